@@ -0,0 +1,90 @@
+//! Traits that [subenum](https://docs.rs/subenum) implements for the enums it
+//! generates, for code that's generic over subenums.
+//!
+//! Give a parent subenum's `runtime` option, and each of its subenums
+//! implements [`SubsetOf`] it. Every type gets [`Narrow::narrow`], to convert to
+//! any of its subenums, and every `Result` gets [`NarrowErr::narrow_err`],
+//! to handle only some of its errors.
+//!
+//! ```rust
+//! use subenum::subenum;
+//! use subenum_runtime::{Narrow, SubsetOf};
+//!
+//! #[subenum(Edible, Tree, runtime)]
+//! #[derive(Clone, Debug, PartialEq)]
+//! pub enum Plant {
+//!     #[subenum(Edible)]
+//!     Basil,
+//!     #[subenum(Edible, Tree)]
+//!     Apple,
+//!     #[subenum(Tree)]
+//!     Pine,
+//! }
+//!
+//! fn count<C: SubsetOf<Plant>>(plants: &[Plant]) -> usize {
+//!     plants.iter().filter(|plant| C::contains(plant)).count()
+//! }
+//!
+//! fn main() {
+//!     let plants = [Plant::Basil, Plant::Apple, Plant::Pine];
+//!     assert_eq!(count::<Edible>(&plants), 2);
+//!     assert_eq!(Tree::VARIANTS, &["Apple", "Pine"]);
+//!
+//!     assert_eq!(Plant::Apple.narrow::<Tree>(), Ok(Tree::Apple));
+//!     assert!(Plant::Pine.narrow::<Edible>().is_err());
+//! }
+//! ```
+//!
+//! If you re-export this crate, tell subenum where to find it instead, with
+//! `#[subenum(Edible, crate = path::to::subenum_runtime)]`.
+#![no_std]
+
+/// A subenum of `P`, whose variants are some of `P`'s.
+pub trait SubsetOf<P>: Sized {
+    /// The error returned when a `P` isn't one of our variants.
+    type Error;
+
+    /// The names of our variants, in the order they're declared in `P`.
+    const VARIANTS: &'static [&'static str];
+
+    /// Converts this into the parent.
+    fn widen(self) -> P;
+
+    /// Converts the parent into this, if it's one of our variants.
+    fn narrow(parent: P) -> Result<Self, Self::Error>;
+
+    /// Converts the parent into this, if it's one of our variants, or gives
+    /// it back unchanged.
+    fn try_narrow(parent: P) -> Result<Self, P>;
+
+    /// Returns whether `parent` is one of our variants.
+    fn contains(parent: &P) -> bool;
+}
+
+/// An extension trait to convert any type to one of its subenums.
+pub trait Narrow: Sized {
+    /// Converts this into the subenum `C`, if it's one of its variants.
+    fn narrow<C: SubsetOf<Self>>(self) -> Result<C, C::Error> {
+        C::narrow(self)
+    }
+}
+
+impl<P> Narrow for P {}
+
+/// An extension trait to narrow the error of a `Result` to one of its
+/// subenums.
+pub trait NarrowErr<T, P> {
+    /// Narrows the error to the subenum `C`, leaving it in the outer
+    /// `Result` if it isn't one of `C`'s variants. This lets `?` pass those
+    /// on while the rest are handled.
+    fn narrow_err<C: SubsetOf<P>>(self) -> Result<Result<T, C>, P>;
+}
+
+impl<T, P> NarrowErr<T, P> for Result<T, P> {
+    fn narrow_err<C: SubsetOf<P>>(self) -> Result<Result<T, C>, P> {
+        match self {
+            Ok(value) => Ok(Ok(value)),
+            Err(err) => C::try_narrow(err).map(Err),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use subenum::subenum;
+use subenum_runtime::NarrowErr;
+
+#[subenum(Io, Parse(Syntax), runtime)]
+#[derive(Clone, Debug, PartialEq)]
+enum Error {
+    #[subenum(Io)]
+    NotFound,
+    #[subenum(Io)]
+    Denied,
+    #[subenum(Syntax)]
+    Unexpected(char),
+    #[subenum(Syntax)]
+    Unclosed,
+    #[subenum(Parse)]
+    Overflow,
+}
+
+fn tokenize(input: &str) -> Result<u8, Syntax> {
+    match input.chars().find(|c| !c.is_ascii_digit()) {
+        Some('(') => Err(Syntax::Unclosed),
+        Some(c) => Err(Syntax::Unexpected(c)),
+        None => Ok(input.len() as u8),
+    }
+}
+
+fn parse(input: &str) -> Result<u8, Parse> {
+    let len = tokenize(input)?;
+    if len > 3 {
+        return Err(Parse::Overflow);
+    }
+    input.parse().map_err(|_| Parse::Overflow)
+}
+
+fn load(input: &str) -> Result<u8, Error> {
+    if input.is_empty() {
+        return Err(Io::NotFound.into());
+    }
+    Ok(parse(input)?)
+}
+
+#[test]
+fn test_widen_with_question_mark() {
+    assert_eq!(parse("12"), Ok(12));
+    assert_eq!(parse("1x"), Err(Parse::Unexpected('x')));
+    assert_eq!(load("1("), Err(Error::Unclosed));
+    assert_eq!(load("1234"), Err(Error::Overflow));
+    assert_eq!(load(""), Err(Error::NotFound));
+}
+
+// Recover from syntax errors, passing anything else on.
+fn load_or_zero(input: &str) -> Result<u8, Error> {
+    match load(input).narrow_err::<Syntax>()? {
+        Ok(value) => Ok(value),
+        Err(Syntax::Unexpected(_) | Syntax::Unclosed) => Ok(0),
+    }
+}
+
+#[test]
+fn test_narrow_err() {
+    assert_eq!(load("7").narrow_err::<Io>(), Ok(Ok(7)));
+    assert_eq!(load("").narrow_err::<Io>(), Ok(Err(Io::NotFound)));
+    assert_eq!(load("1x").narrow_err::<Io>(), Err(Error::Unexpected('x')));
+    assert_eq!(load("1234").narrow_err::<Parse>(), Ok(Err(Parse::Overflow)));
+
+    assert_eq!(load_or_zero("1x"), Ok(0));
+    assert_eq!(load_or_zero("5"), Ok(5));
+    assert_eq!(load_or_zero(""), Err(Error::NotFound));
+    assert_eq!(
+        Err::<u8, _>(Error::Denied).narrow_err::<Io>(),
+        Ok(Err(Io::Denied))
+    );
+}
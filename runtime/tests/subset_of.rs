@@ -0,0 +1,92 @@
+use subenum::subenum;
+use subenum_runtime::{Narrow, SubsetOf};
+
+#[subenum(Edible, Tree, Everything, runtime)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Edible, Everything)]
+    Basil(String),
+    #[subenum(Edible, Tree, Everything)]
+    Apple { height: u32 },
+    #[subenum(Tree, Everything)]
+    Pine,
+}
+
+// The plants that aren't in `C`.
+fn rejected<C: SubsetOf<Plant>>(plants: Vec<Plant>) -> Vec<Plant> {
+    plants
+        .into_iter()
+        .filter(|plant| !C::contains(plant))
+        .collect()
+}
+
+#[test]
+fn test_subset_of() {
+    assert_eq!(Edible::VARIANTS, &["Basil", "Apple"]);
+    assert_eq!(Tree::VARIANTS, &["Apple", "Pine"]);
+
+    assert_eq!(Tree::Pine.widen(), Plant::Pine);
+    assert_eq!(
+        <Edible as SubsetOf<Plant>>::narrow(Plant::Basil("thai".into())),
+        Ok(Edible::Basil("thai".into()))
+    );
+    let err = <Edible as SubsetOf<Plant>>::narrow(Plant::Pine).unwrap_err();
+    assert_eq!(err.into_inner(), Plant::Pine);
+
+    let plants = vec![Plant::Pine, Plant::Apple { height: 3 }];
+    assert_eq!(rejected::<Edible>(plants.clone()), vec![Plant::Pine]);
+    assert!(rejected::<Tree>(plants).is_empty());
+}
+
+#[test]
+fn test_narrow() {
+    assert_eq!(
+        Plant::Apple { height: 1 }.narrow::<Tree>(),
+        Ok(Tree::Apple { height: 1 })
+    );
+    assert!(Plant::Pine.narrow::<Edible>().is_err());
+
+    let everything: Result<Everything, core::convert::Infallible> = Plant::Pine.narrow();
+    assert_eq!(everything, Ok(Everything::Pine));
+}
+
+#[subenum(Small, runtime)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Wrapper<T, U> {
+    #[subenum(Small)]
+    One(T),
+    Two(U),
+}
+
+#[test]
+fn test_generic_subset_of() {
+    fn widen_all<C: SubsetOf<Wrapper<u8, u16>>>(values: Vec<C>) -> Vec<Wrapper<u8, u16>> {
+        values.into_iter().map(C::widen).collect()
+    }
+
+    assert_eq!(widen_all(vec![Small::One(1)]), vec![Wrapper::One(1)]);
+    assert_eq!(
+        Wrapper::<u8, u16>::One(2).narrow::<Small<u8>>(),
+        Ok(Small::One(2))
+    );
+    assert!(Wrapper::<u8, u16>::Two(3).narrow::<Small<u8>>().is_err());
+}
+
+mod reexport {
+    pub use subenum_runtime as runtime;
+}
+
+#[subenum(Lit, crate = crate::reexport::runtime)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Light {
+    #[subenum(Lit)]
+    On,
+    Off,
+}
+
+#[test]
+fn test_crate_path() {
+    assert_eq!(Light::On.narrow::<Lit>(), Ok(Lit::On));
+    assert!(<Lit as SubsetOf<Light>>::contains(&Light::On));
+    assert!(!<Lit as SubsetOf<Light>>::contains(&Light::Off));
+}
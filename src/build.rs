@@ -1,8 +1,9 @@
-use alloc::{format, vec::Vec};
+use alloc::{string::ToString, vec::Vec};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 use syn::{
-    punctuated::Punctuated, Attribute, DeriveInput, Generics, Ident, Token, TypeParamBound, Variant,
+    punctuated::Punctuated, Attribute, DeriveInput, GenericParam, Generics, Ident, Token,
+    TypeParamBound, Variant,
 };
 
 use crate::{
@@ -11,6 +12,34 @@ use crate::{
     snake_case,
 };
 
+mod accessors;
+mod bulk;
+mod classify;
+mod const_fns;
+mod error;
+mod index;
+mod introspect;
+mod iter;
+mod layout;
+mod map;
+mod pattern;
+mod repr;
+mod runs;
+mod set;
+mod siblings;
+mod subset_of;
+mod unchecked;
+mod views;
+
+pub use classify::build_classify;
+pub use index::build_parent_index;
+pub use introspect::build_introspection;
+pub use iter::build_iter_ext;
+pub use map::build_parent_map;
+pub use repr::repr_hints;
+pub use runs::build_parent_variant_name;
+pub use set::build_parent_set;
+
 // Add a bound to generics
 fn add_bound(generics: &mut Generics, bound: TypeParamBound) {
     for param in generics.type_params_mut() {
@@ -20,6 +49,124 @@ fn add_bound(generics: &mut Generics, bound: TypeParamBound) {
     }
 }
 
+// Only the doc attributes of `attrs`, for generated items that don't derive
+// anything that could make use of the others.
+fn doc_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("doc"))
+}
+
+// The variants of the enum being subenum'd.
+fn parent_variants(parent: &DeriveInput) -> &Punctuated<Variant, Token![,]> {
+    match &parent.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => unreachable!("subenum may only be used on enums."),
+    }
+}
+
+// Implement a conversion from `from` to `to`, bound as `value` in the match.
+// `accepted` pairs patterns with the values they convert to. If no `rejected`
+// arms are needed, the conversion can't fail and we implement `From`;
+// otherwise, `TryFrom` with `error`.
+fn build_conversion(
+    generics: &Generics,
+    from: TokenStream2,
+    to: TokenStream2,
+    error: TokenStream2,
+    accepted: impl IntoIterator<Item = (TokenStream2, TokenStream2)>,
+    rejected: &[TokenStream2],
+) -> TokenStream2 {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    if rejected.is_empty() {
+        let arms = accepted
+            .into_iter()
+            .map(|(pat, value)| quote!(#pat => #value));
+        return quote!(
+            #[automatically_derived]
+            impl #impl_generics core::convert::From<#from> for #to #where_clause {
+                fn from(value: #from) -> Self {
+                    match value {
+                        #(#arms,)*
+                    }
+                }
+            }
+        );
+    }
+
+    let arms = accepted
+        .into_iter()
+        .map(|(pat, value)| quote!(#pat => Ok(#value)));
+    quote!(
+        #[automatically_derived]
+        impl #impl_generics core::convert::TryFrom<#from> for #to #where_clause {
+            type Error = #error;
+
+            fn try_from(value: #from) -> core::result::Result<Self, <Self as core::convert::TryFrom<#from>>::Error> {
+                match value {
+                    #(#arms,)*
+                    #(#rejected,)*
+                }
+            }
+        }
+    )
+}
+
+// The variants of a fieldless enum, for an `option` that needs one.
+fn fieldless_variants<'a>(
+    ident: &Ident,
+    variants: impl IntoIterator<Item = &'a Variant>,
+    option: &str,
+) -> Vec<&'a Ident> {
+    variants
+        .into_iter()
+        .map(|v| {
+            if !v.fields.is_empty() {
+                panic!("`{ident}` has variants with fields, so it can't have `{option}`");
+            }
+            &v.ident
+        })
+        .collect()
+}
+
+// The parent's generic params that the child doesn't have, for methods on the
+// child that take a parent. They can't go on the impl, as nothing there would
+// constrain them.
+fn extra_generics(child: &Generics, parent: &Generics) -> Generics {
+    let mut extra = Generics::default();
+    for param in &parent.params {
+        if !child.params.iter().any(|p| same_param(p, param)) {
+            let mut param = param.clone();
+            match &mut param {
+                GenericParam::Type(t) => {
+                    t.eq_token = None;
+                    t.default = None;
+                }
+                GenericParam::Const(c) => {
+                    c.eq_token = None;
+                    c.default = None;
+                }
+                GenericParam::Lifetime(_) => {}
+            }
+            extra.params.push(param);
+        }
+    }
+    if !extra.params.is_empty() {
+        extra.lt_token = Some(Default::default());
+        extra.gt_token = Some(Default::default());
+    }
+    extra.where_clause = parent.where_clause.clone();
+    extra
+}
+
+fn same_param(a: &GenericParam, b: &GenericParam) -> bool {
+    match (a, b) {
+        (GenericParam::Type(a), GenericParam::Type(b)) => a.ident == b.ident,
+        (GenericParam::Lifetime(a), GenericParam::Lifetime(b)) => a.lifetime == b.lifetime,
+        (GenericParam::Const(a), GenericParam::Const(b)) => a.ident == b.ident,
+        _ => false,
+    }
+}
+
 // Map a variant from an enum definition to how it would be used in a match
 // E.g.
 // * Foo -> Foo
@@ -47,6 +194,27 @@ fn variant_to_unary_pat(variant: &Variant) -> TokenStream2 {
 }
 
 impl Enum {
+    // Match arms for every variant of `parent` that isn't part of this enum,
+    // evaluating to `body` with the name of the rejected variant as a string
+    // literal. The arms don't bind anything, so the matched value can still be
+    // used. There are none if this enum has all of the parent's variants.
+    fn rejected_arms(
+        &self,
+        parent: &DeriveInput,
+        body: impl Fn(&str) -> TokenStream2,
+    ) -> Vec<TokenStream2> {
+        let parent_ident = &parent.ident;
+        parent_variants(parent)
+            .iter()
+            .filter(|variant| !self.contains(&variant.ident))
+            .map(|variant| {
+                let ident = &variant.ident;
+                let body = body(&ident.to_string());
+                quote!(#parent_ident::#ident { .. } => #body)
+            })
+            .collect()
+    }
+
     fn build_inherited_derive<'a>(
         &self,
         parent: &DeriveInput,
@@ -60,6 +228,20 @@ impl Enum {
 
         match derive {
             Derive::PartialEq => {
+                if let Some(runs) = self.runs_to_parent(parent) {
+                    let eq = self.build_runs_partial_eq(parent_ident, &runs);
+                    return quote!(
+                        #eq
+
+                        #[automatically_derived]
+                        impl PartialEq<#child_ident> for #parent_ident {
+                            fn eq(&self, other: &#child_ident) -> bool {
+                                other == self
+                            }
+                        }
+                    );
+                }
+
                 let mut generics = parent.generics.clone();
                 add_bound(&mut generics, derive.as_bound());
                 let (parent_impl, parent_ty, parent_where) = generics.split_for_impl();
@@ -96,15 +278,15 @@ impl Enum {
         }
     }
 
-    pub fn build(&self, parent: &mut DeriveInput, child_attrs: &[Attribute]) -> TokenStream2 {
-        if self.ident == parent.ident {
-            parent.attrs.extend(self.attributes.clone());
-            return Default::default();
-        }
-
+    pub fn build(
+        &self,
+        parent: &DeriveInput,
+        child_attrs: &[Attribute],
+        complement: Option<&Enum>,
+    ) -> TokenStream2 {
         let attributes = self.attributes.clone();
         let variants = self
-            .variants
+            .layout_variants(parent)
             .iter()
             .zip(self.variants_attributes.clone())
             .map(|(variant, attribute)| quote! { #(#attribute)* #variant })
@@ -114,18 +296,27 @@ impl Enum {
         let child_ident = &self.ident;
         let parent_ident = &parent.ident;
 
-        let error = format_ident!("{child_ident}ConvertError");
-
-        #[cfg(not(feature = "error_trait"))]
-        let error_trait_impl = quote!();
-        #[cfg(all(feature = "error_trait", feature = "std"))]
-        let error_trait_impl = quote!(
-            impl std::error::Error for #error {}
-        );
-        #[cfg(all(feature = "error_trait", not(feature = "std")))]
-        let error_trait_impl = quote!(
-            impl core::error::Error for #error {}
-        );
+        let error = self.error_ident();
+        let rejected_arms =
+            self.rejected_arms(parent, |variant| quote!(Err(#error::new(value, #variant))));
+        let repr_conversions = self.build_repr_conversions(parent);
+        // If we have every variant, converting from the parent can't fail, so
+        // there's no need for an error type, unless converting from its `repr`
+        // needs one.
+        let error_type = (!rejected_arms.is_empty() || !repr_conversions.is_empty())
+            .then(|| self.build_error(parent));
+        let views = self.refs.then(|| self.build_views(parent, child_attrs));
+        let accessors = self.build_accessors(parent);
+        let pattern_macro = self.build_pattern_macro(parent);
+        let unchecked = self.build_unchecked(parent);
+        let const_fns = self.build_const_fns(parent, child_attrs);
+        let index = self.build_index(parent);
+        let set = self.set.then(|| self.build_set(parent, child_attrs));
+        let map = self.map.then(|| self.build_map(parent, child_attrs));
+        let layout_casts = self
+            .layout_compatible
+            .then(|| self.build_layout_casts(parent));
+        let bulk_conversions = self.build_bulk_conversions(parent);
 
         let pats: Vec<TokenStream2> = self.variants.iter().map(variant_to_unary_pat).collect();
 
@@ -133,9 +324,53 @@ impl Enum {
             .iter()
             .map(|pat| quote!(#child_ident::#pat => #parent_ident::#pat));
 
-        let try_from_parent_arms = pats
-            .iter()
-            .map(|pat| quote!(#parent_ident::#pat => Ok(#child_ident::#pat)));
+        let (_child_impl, child_ty, child_where) = child_generics.split_for_impl();
+        let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
+
+        // With a complement, a failed conversion hands back the value as that
+        // instead.
+        let (from_parent_error, from_parent_rejected) = match complement {
+            Some(complement) => {
+                let complement_ident = &complement.ident;
+                let (_, complement_ty, _) = complement.generics.split_for_impl();
+                let arms = complement
+                    .variants
+                    .iter()
+                    .map(variant_to_unary_pat)
+                    .map(|pat| quote!(#parent_ident::#pat => Err(#complement_ident::#pat)))
+                    .collect();
+                (quote!(#complement_ident #complement_ty), arms)
+            }
+            None => (quote!(#error<#parent_ident #parent_ty>), rejected_arms),
+        };
+        let from_parent = build_conversion(
+            &parent.generics,
+            quote!(#parent_ident #parent_ty),
+            quote!(#child_ident #child_ty),
+            from_parent_error,
+            pats.iter()
+                .map(|pat| (quote!(#parent_ident::#pat), quote!(#child_ident::#pat))),
+            &from_parent_rejected,
+        );
+
+        // Fieldless enums can convert their discriminants instead, with far
+        // fewer match arms when there are many variants.
+        let conversions = self
+            .build_runs_conversions(parent, complement)
+            .unwrap_or_else(|| {
+                quote!(
+                    #[automatically_derived]
+                    impl #parent_impl core::convert::From<#child_ident #child_ty> for #parent_ident #parent_ty #parent_where {
+                        fn from(child: #child_ident #child_ty) -> Self {
+                            match child {
+                                #(#from_child_arms),*
+                            }
+                        }
+                    }
+
+                    #from_parent
+                )
+            });
 
         let inherited_derives = self
             .derives
@@ -144,14 +379,6 @@ impl Enum {
 
         let vis = &parent.vis;
 
-        let (_child_impl, child_ty, child_where) = child_generics.split_for_impl();
-
-        let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
-
-        let error_doc = format!(
-            "An error type used for converting from [`{parent_ident}`] to [`{child_ident}`]."
-        );
-
         quote!(
             #(#attributes)*
             #(#child_attrs)*
@@ -161,38 +388,31 @@ impl Enum {
 
             #(#inherited_derives)*
 
-            #[doc = #error_doc]
-            #[derive(Copy, Clone, Debug)]
-            #vis struct #error;
+            #error_type
 
-            impl core::fmt::Display for #error {
-                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                    core::fmt::Debug::fmt(self, f)
-                }
-            }
+            #views
 
-            #error_trait_impl
+            #accessors
 
-            #[automatically_derived]
-            impl #parent_impl core::convert::From<#child_ident #child_ty> for #parent_ident #parent_ty #parent_where {
-                fn from(child: #child_ident #child_ty) -> Self {
-                    match child {
-                        #(#from_child_arms),*
-                    }
-                }
-            }
+            #pattern_macro
 
-            #[automatically_derived]
-            impl #parent_impl core::convert::TryFrom<#parent_ident #parent_ty> for #child_ident #child_ty #parent_where {
-                type Error = #error;
+            #unchecked
 
-                fn try_from(parent: #parent_ident #parent_ty) -> core::result::Result<Self, <Self as core::convert::TryFrom<#parent_ident #parent_ty>>::Error> {
-                    match parent {
-                        #(#try_from_parent_arms),*,
-                        _ => Err(#error)
-                    }
-                }
-            }
+            #const_fns
+
+            #repr_conversions
+
+            #index
+
+            #set
+
+            #map
+
+            #layout_casts
+
+            #bulk_conversions
+
+            #conversions
         )
     }
 }
@@ -0,0 +1,111 @@
+use alloc::{format, string::ToString, vec::Vec};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use super::{
+    variant_to_unary_pat,
+    views::{view_lifetime, with_view_lifetime},
+};
+use crate::r#enum::Enum;
+
+impl Enum {
+    /// Build the `is_{child}`, `into_{child}` and, with `refs`,
+    /// `as_{child}` and `as_{child}_mut` methods on the parent.
+    pub fn build_accessors(&self, parent: &DeriveInput) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let vis = &parent.vis;
+        let snake = self.ident.to_string().to_snake_case();
+
+        let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
+        let (_, child_ty, _) = self.generics.split_for_impl();
+
+        let is_fn = format_ident!("is_{snake}");
+        let into_fn = format_ident!("into_{snake}");
+        let is_doc = format!("Returns whether this is one of [`{child_ident}`]'s variants.");
+        let into_doc = format!(
+            "Converts this into [`{child_ident}`], or returns it unchanged if it isn't one of \
+            its variants."
+        );
+
+        let variant_idents = self.variants.iter().map(|v| &v.ident);
+        let pats: Vec<TokenStream2> = self.variants.iter().map(variant_to_unary_pat).collect();
+        let into_arms = pats
+            .iter()
+            .map(|pat| quote!(#parent_ident::#pat => Ok(#child_ident::#pat)));
+        let rejected_arms = self.rejected_arms(parent, |_| quote!(Err(self)));
+
+        let as_fns = self.refs.then(|| {
+            let lifetime = view_lifetime();
+            let ref_ident = self.ref_ident();
+            let mut_ident = self.mut_ident();
+            let view_generics = with_view_lifetime(&self.generics);
+            let (_, view_ty, _) = view_generics.split_for_impl();
+            let as_fn = format_ident!("as_{snake}");
+            let as_mut_fn = format_ident!("as_{snake}_mut");
+            let as_doc = format!(
+                "Borrows this as a [`{ref_ident}`], if it's one of [`{child_ident}`]'s variants."
+            );
+            let as_mut_doc = format!(
+                "Mutably borrows this as a [`{mut_ident}`], if it's one of [`{child_ident}`]'s \
+                variants."
+            );
+
+            quote!(
+                #[doc = #as_doc]
+                #vis fn #as_fn<#lifetime>(&#lifetime self) -> core::option::Option<#ref_ident #view_ty> {
+                    core::convert::TryFrom::try_from(self).ok()
+                }
+
+                #[doc = #as_mut_doc]
+                #vis fn #as_mut_fn<#lifetime>(&#lifetime mut self) -> core::option::Option<#mut_ident #view_ty> {
+                    core::convert::TryFrom::try_from(self).ok()
+                }
+            )
+        });
+
+        let (is_body, into_body) = match self.runs_from_parent(parent) {
+            Some(runs) => {
+                let read = runs.read(quote!(self));
+                let pattern = runs.pattern();
+                let child_value = runs.convert(
+                    runs.read(quote!(&self)),
+                    quote!(#child_ident),
+                    quote!(return Err(self)),
+                );
+                (
+                    quote!(core::matches!(#read, #pattern)),
+                    quote!(Ok(#child_value)),
+                )
+            }
+            None => (
+                quote!(core::matches!(self, #(#parent_ident::#variant_idents { .. })|*)),
+                quote!(
+                    match self {
+                        #(#into_arms,)*
+                        #(#rejected_arms,)*
+                    }
+                ),
+            ),
+        };
+
+        quote!(
+            #[allow(dead_code)]
+            impl #parent_impl #parent_ident #parent_ty #parent_where {
+                #[doc = #is_doc]
+                #vis fn #is_fn(&self) -> bool {
+                    #is_body
+                }
+
+                #[doc = #into_doc]
+                #vis fn #into_fn(self) -> core::result::Result<#child_ident #child_ty, Self> {
+                    #into_body
+                }
+
+                #as_fns
+            }
+        )
+    }
+}
@@ -0,0 +1,162 @@
+use alloc::{format, string::ToString};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use super::extra_generics;
+use crate::r#enum::Enum;
+
+impl Enum {
+    /// Build conversions of `Vec`s, boxed slices and, if we're
+    /// `layout_compatible`, slices of the parent. With compatible layouts,
+    /// they reuse the parent's memory once its values are checked; otherwise
+    /// they convert each value.
+    pub fn build_bulk_conversions(&self, parent: &DeriveInput) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let vis = &parent.vis;
+        let is_fn = format_ident!("is_{}", self.ident.to_string().to_snake_case());
+
+        let (child_impl, child_ty, child_where) = self.generics.split_for_impl();
+        let (_, parent_ty, _) = parent.generics.split_for_impl();
+        let extra = extra_generics(&self.generics, &parent.generics);
+        let extra_where = &extra.where_clause;
+        let parent = quote!(#parent_ident #parent_ty);
+        let check = self.layout_compatible.then(|| {
+            let check = self.layout_check_ident();
+            quote!(let () = <#parent>::#check;)
+        });
+
+        let slices = self.layout_compatible.then(|| {
+            let try_from_slice_doc = format!(
+                "Borrows a slice of [`{parent_ident}`]s as [`{child_ident}`]s, without copying \
+                them, or returns it if any isn't one of our variants."
+            );
+            let as_parent_slice_doc = format!(
+                "Borrows a slice of [`{child_ident}`]s as [`{parent_ident}`]s, without copying \
+                them."
+            );
+
+            quote!(
+                #[doc = #try_from_slice_doc]
+                #vis fn try_from_slice #extra(values: &[#parent]) -> core::result::Result<&[Self], &[#parent]> #extra_where {
+                    if !values.iter().all(#parent_ident::#is_fn) {
+                        return Err(values);
+                    }
+                    #check
+                    // SAFETY: Each value is one of our variants, and our
+                    // layouts are compatible.
+                    Ok(unsafe { core::slice::from_raw_parts(values.as_ptr() as *const Self, values.len()) })
+                }
+
+                #[doc = #as_parent_slice_doc]
+                #vis fn as_parent_slice #extra(values: &[Self]) -> &[#parent] #extra_where {
+                    #check
+                    // SAFETY: Our layouts are compatible, and each of our
+                    // variants is one of the parent's.
+                    unsafe { core::slice::from_raw_parts(values.as_ptr() as *const #parent, values.len()) }
+                }
+            )
+        });
+
+        #[cfg(not(feature = "std"))]
+        let owned = quote!();
+        #[cfg(feature = "std")]
+        let owned = {
+            let (try_from_vec_body, into_parent_vec_body) = if self.layout_compatible {
+                (
+                    quote!(
+                        #check
+                        let mut values = core::mem::ManuallyDrop::new(values);
+                        // SAFETY: Each value is one of our variants, and our
+                        // layouts are compatible, so the allocation holds the
+                        // same number of our values.
+                        Ok(unsafe {
+                            std::vec::Vec::from_raw_parts(
+                                values.as_mut_ptr() as *mut Self,
+                                values.len(),
+                                values.capacity(),
+                            )
+                        })
+                    ),
+                    quote!(
+                        #check
+                        let mut values = core::mem::ManuallyDrop::new(values);
+                        // SAFETY: Our layouts are compatible, and each of our
+                        // variants is one of the parent's.
+                        unsafe {
+                            std::vec::Vec::from_raw_parts(
+                                values.as_mut_ptr() as *mut #parent,
+                                values.len(),
+                                values.capacity(),
+                            )
+                        }
+                    ),
+                )
+            } else {
+                (
+                    quote!(
+                        Ok(values
+                            .into_iter()
+                            .map(|value| match <Self as core::convert::TryFrom<#parent>>::try_from(value) {
+                                Ok(value) => value,
+                                Err(_) => core::unreachable!(),
+                            })
+                            .collect())
+                    ),
+                    quote!(values.into_iter().map(core::convert::From::from).collect()),
+                )
+            };
+
+            let try_from_vec_doc = format!(
+                "Converts a `Vec` of [`{parent_ident}`]s into [`{child_ident}`]s, or returns it \
+                if any isn't one of our variants."
+            );
+            let into_parent_vec_doc =
+                format!("Converts a `Vec` of [`{child_ident}`]s into [`{parent_ident}`]s.");
+            let try_from_boxed_slice_doc = format!(
+                "Converts a boxed slice of [`{parent_ident}`]s into [`{child_ident}`]s, or \
+                returns it if any isn't one of our variants."
+            );
+            let into_parent_boxed_slice_doc =
+                format!("Converts a boxed slice of [`{child_ident}`]s into [`{parent_ident}`]s.");
+
+            quote!(
+                #[doc = #try_from_vec_doc]
+                #vis fn try_from_vec #extra(values: std::vec::Vec<#parent>) -> core::result::Result<std::vec::Vec<Self>, std::vec::Vec<#parent>> #extra_where {
+                    if !values.iter().all(#parent_ident::#is_fn) {
+                        return Err(values);
+                    }
+                    #try_from_vec_body
+                }
+
+                #[doc = #into_parent_vec_doc]
+                #vis fn into_parent_vec #extra(values: std::vec::Vec<Self>) -> std::vec::Vec<#parent> #extra_where {
+                    #into_parent_vec_body
+                }
+
+                #[doc = #try_from_boxed_slice_doc]
+                #vis fn try_from_boxed_slice #extra(values: std::boxed::Box<[#parent]>) -> core::result::Result<std::boxed::Box<[Self]>, std::boxed::Box<[#parent]>> #extra_where {
+                    Self::try_from_vec(values.into_vec())
+                        .map(std::vec::Vec::into_boxed_slice)
+                        .map_err(std::vec::Vec::into_boxed_slice)
+                }
+
+                #[doc = #into_parent_boxed_slice_doc]
+                #vis fn into_parent_boxed_slice #extra(values: std::boxed::Box<[Self]>) -> std::boxed::Box<[#parent]> #extra_where {
+                    Self::into_parent_vec(values.into_vec()).into_boxed_slice()
+                }
+            )
+        };
+
+        quote!(
+            #[allow(dead_code)]
+            impl #child_impl #child_ident #child_ty #child_where {
+                #slices
+
+                #owned
+            }
+        )
+    }
+}
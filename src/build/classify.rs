@@ -0,0 +1,142 @@
+use alloc::{format, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Attribute, DeriveInput, Ident};
+
+use super::{doc_attrs, parent_variants, variant_to_unary_pat};
+use crate::{derive::std_derives, r#enum::Enum};
+
+/// Build `{Parent}Class`, with a variant for each top-level subenum and one
+/// for the variants in none of them, along with `{Parent}::classify`.
+pub fn build_classify(
+    parent: &DeriveInput,
+    children: &[Enum],
+    parent_attrs: &[Attribute],
+) -> TokenStream2 {
+    let parent_ident = &parent.ident;
+    let class_ident = format_ident!("{}Class", parent_ident);
+    let other_ident = format_ident!("{}Other", parent_ident);
+    let vis = &parent.vis;
+
+    // Nested subenums are contained in the ones they're declared within, so
+    // only the top-level ones can partition the parent.
+    let classes: Vec<&Enum> = children.iter().filter(|e| e.superset.is_none()).collect();
+
+    let mut other = Enum::new(other_ident.clone(), Vec::new(), Vec::new());
+    let mut arms = Vec::new();
+    for variant in parent_variants(parent) {
+        let mut containing = classes.iter().filter(|e| e.contains(&variant.ident));
+        let pat = variant_to_unary_pat(variant);
+
+        // The `{Parent}Class` variant to wrap this in, and the enum it holds.
+        let (class, ty): (Ident, &Ident) = match (containing.next(), containing.next()) {
+            (Some(a), Some(b)) => panic!(
+                "`classify` needs subenums that don't overlap, but `{}` is in both `{}` and `{}`",
+                variant.ident, a.ident, b.ident
+            ),
+            (Some(e), None) => (e.ident.clone(), &e.ident),
+            (None, _) => {
+                let mut variant = variant.clone();
+                variant.attrs = doc_attrs(&variant.attrs).cloned().collect();
+                variant.discriminant = None;
+                other.push_variant(variant, Vec::new());
+                (format_ident!("Other"), &other_ident)
+            }
+        };
+        arms.push(quote!(#parent_ident::#pat => #class_ident::#class(#ty::#pat)));
+    }
+    other.compute_generics(&parent.generics);
+
+    // The variants left over go in an `Other` class holding a
+    // `{Parent}Other`, which a subenum of either name would clash with.
+    if !other.variants.is_empty() {
+        if let Some(class) = classes.iter().find(|e| e.ident == "Other") {
+            panic!(
+                "`classify` puts `{}`'s variants that are in no subenum in an `Other` variant \
+                of `{class_ident}`, so it can't also have one for the subenum `{}`; rename it, \
+                or put `{}` in a subenum",
+                parent_ident,
+                class.ident,
+                other.variants.first().unwrap().ident,
+            );
+        }
+        if let Some(child) = children.iter().find(|e| e.ident == other_ident) {
+            panic!(
+                "`classify` generates `{}` for `{}`'s variants that are in no subenum, so it \
+                can't also be the name of a subenum",
+                child.ident, parent_ident,
+            );
+        }
+    }
+
+    let derives = std_derives(parent_attrs);
+    let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
+    let (other_impl, other_ty, other_where) = other.generics.split_for_impl();
+
+    let class_variants = classes.iter().map(|e| {
+        let ident = &e.ident;
+        let (_, ty, _) = e.generics.split_for_impl();
+        let doc = format!("A [`{parent_ident}`] that is an [`{ident}`].");
+        quote!(#[doc = #doc] #ident(#ident #ty))
+    });
+
+    // Without any variants left over, there's no need for `{Parent}Other`.
+    let other_enum = (!other.variants.is_empty()).then(|| {
+        let other_doc = format!("The variants of [`{parent_ident}`] that aren't in any subenum.");
+        let other_variant_doc = format!("A [`{parent_ident}`] that isn't in any subenum.");
+        let other_variants = &other.variants;
+        let pats = other.variants.iter().map(variant_to_unary_pat);
+        let other_generics = &other.generics;
+
+        (
+            quote!(#[doc = #other_variant_doc] Other(#other_ident #other_ty)),
+            quote!(
+                #[doc = #other_doc]
+                #[derive(#(#derives),*)]
+                #[allow(dead_code)]
+                #vis enum #other_ident #other_generics #other_where {
+                    #other_variants
+                }
+
+                #[automatically_derived]
+                impl #other_impl core::convert::From<#other_ident #other_ty> for #parent_ident #parent_ty #parent_where {
+                    fn from(other: #other_ident #other_ty) -> Self {
+                        match other {
+                            #(#other_ident::#pats => #parent_ident::#pats),*
+                        }
+                    }
+                }
+            ),
+        )
+    });
+    let (other_variant, other_enum) = other_enum.unzip();
+
+    let class_doc = format!(
+        "The subenum a [`{parent_ident}`] belongs to, holding it narrowed to that subenum."
+    );
+    let classify_doc =
+        "Narrow this to whichever subenum it belongs to, for matching on each with its own type.";
+    let parent_generics = &parent.generics;
+
+    quote!(
+        #[doc = #class_doc]
+        #[derive(#(#derives),*)]
+        #[allow(dead_code)]
+        #vis enum #class_ident #parent_generics #parent_where {
+            #(#class_variants,)*
+            #other_variant
+        }
+
+        #other_enum
+
+        #[allow(dead_code)]
+        impl #parent_impl #parent_ident #parent_ty #parent_where {
+            #[doc = #classify_doc]
+            #vis fn classify(self) -> #class_ident #parent_ty {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,108 @@
+use alloc::{format, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_quote, Attribute, DeriveInput};
+
+use super::{extra_generics, variant_to_unary_pat};
+use crate::{derive::derives_trait, r#enum::Enum};
+
+impl Enum {
+    /// Build `const fn`s converting to and from the parent, as `From` and
+    /// `TryFrom` can't be used in `const` contexts. These are only possible
+    /// if the values they drop are `Copy`, so there's nothing to run at
+    /// compile time; we build `to_parent` if we're `Copy`, and
+    /// `try_from_parent` if the parent is, too.
+    pub fn build_const_fns(&self, parent: &DeriveInput, child_attrs: &[Attribute]) -> TokenStream2 {
+        let parent_is_copy = derives_trait(child_attrs, "Copy");
+        if !parent_is_copy && !derives_trait(&self.attributes, "Copy") {
+            return quote!();
+        }
+
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let vis = &parent.vis;
+
+        let (child_impl, child_ty, child_where) = self.generics.split_for_impl();
+        let (_, parent_ty, _) = parent.generics.split_for_impl();
+
+        // Dropping a generic value in a `const fn` needs it to be `Copy`.
+        let mut extra = extra_generics(&self.generics, &parent.generics);
+        let copy_bounds: Vec<syn::WherePredicate> = parent
+            .generics
+            .type_params()
+            .map(|param| {
+                let ident = &param.ident;
+                parse_quote!(#ident: core::marker::Copy)
+            })
+            .collect();
+        extra.make_where_clause().predicates.extend(copy_bounds);
+        let extra_where = &extra.where_clause;
+
+        let pats: Vec<TokenStream2> = self.variants.iter().map(variant_to_unary_pat).collect();
+        let to_arms = pats
+            .iter()
+            .map(|pat| quote!(#child_ident::#pat => #parent_ident::#pat));
+        let unreachable = quote!(unsafe { core::hint::unreachable_unchecked() });
+        let to_body = match self.runs_to_parent(parent) {
+            Some(runs) => {
+                runs.convert(runs.read(quote!(&self)), quote!(#parent_ident), unreachable)
+            }
+            None => quote!(
+                match self {
+                    #(#to_arms,)*
+                }
+            ),
+        };
+
+        let to_doc = format!(
+            "Converts this into a [`{parent_ident}`], like [`From`], but usable in `const` \
+            contexts."
+        );
+
+        let try_from_parent = parent_is_copy.then(|| {
+            let from_arms = pats
+                .iter()
+                .map(|pat| quote!(#parent_ident::#pat => core::option::Option::Some(#child_ident::#pat)));
+            let rejected_arms = self.rejected_arms(parent, |_| quote!(core::option::Option::None));
+            let from_body = match self.runs_from_parent(parent) {
+                Some(runs) => {
+                    let child_value = runs.convert(
+                        runs.read(quote!(&value)),
+                        quote!(Self),
+                        quote!(return core::option::Option::None),
+                    );
+                    quote!(core::option::Option::Some(#child_value))
+                }
+                None => quote!(
+                    match value {
+                        #(#from_arms,)*
+                        #(#rejected_arms,)*
+                    }
+                ),
+            };
+            let try_from_doc = format!(
+                "Converts from a [`{parent_ident}`], if it's one of our variants, like \
+                [`TryFrom`], but usable in `const` contexts."
+            );
+
+            quote!(
+                #[doc = #try_from_doc]
+                #vis const fn try_from_parent #extra(value: #parent_ident #parent_ty) -> core::option::Option<Self> #extra_where {
+                    #from_body
+                }
+            )
+        });
+
+        quote!(
+            #[allow(dead_code)]
+            impl #child_impl #child_ident #child_ty #child_where {
+                #[doc = #to_doc]
+                #vis const fn to_parent #extra(self) -> #parent_ident #parent_ty #extra_where {
+                    #to_body
+                }
+
+                #try_from_parent
+            }
+        )
+    }
+}
@@ -0,0 +1,143 @@
+use alloc::{format, string::ToString, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Ident};
+
+use crate::r#enum::Enum;
+
+impl Enum {
+    pub fn error_ident(&self) -> Ident {
+        format_ident!("{}ConvertError", self.ident)
+    }
+
+    pub fn build_error(&self, parent: &DeriveInput) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let error = self.error_ident();
+        let vis = &parent.vis;
+
+        let child_name = child_ident.to_string();
+        let parent_name = parent_ident.to_string();
+        let expected: Vec<_> = self.variants.iter().map(|v| v.ident.to_string()).collect();
+        let expected_list = expected.join(", ");
+
+        // The type of the value that failed to convert, which mustn't share a
+        // name with the parent it may default to.
+        let value = if parent_ident == "P" {
+            format_ident!("Parent")
+        } else {
+            format_ident!("P")
+        };
+
+        #[cfg(not(feature = "error_trait"))]
+        let error_trait_impl = quote!();
+        #[cfg(all(feature = "error_trait", feature = "std"))]
+        let error_trait_impl = quote!(
+            impl<#value: core::fmt::Debug> std::error::Error for #error<#value> {}
+        );
+        #[cfg(all(feature = "error_trait", not(feature = "std")))]
+        let error_trait_impl = quote!(
+            impl<#value: core::fmt::Debug> core::error::Error for #error<#value> {}
+        );
+
+        let error_doc = format!(
+            "An error type used for converting from [`{parent_ident}`] to [`{child_ident}`].\n\n\
+            It owns the value that failed to convert, which can be recovered with\n\
+            [`into_inner`]({error}::into_inner), and records which variant it was, if any."
+        );
+
+        let variant_doc = format!(
+            "The name of the rejected [`{parent_ident}`] variant, or `None` if the value wasn't \
+            one of [`{parent_ident}`]'s discriminants."
+        );
+        let parent_name_doc = format!("The name of the parent type, `{parent_ident}`.");
+        let child_name_doc = format!("The name of the type being converted to, `{child_ident}`.");
+        let expected_doc = format!("The names of the variants that [`{child_ident}`] accepts.");
+
+        // When the parent can be named without any generic arguments, let the
+        // error default to it so `{Child}ConvertError` keeps working on its own.
+        let error_default = parent
+            .generics
+            .params
+            .iter()
+            .all(|param| match param {
+                syn::GenericParam::Type(t) => t.default.is_some(),
+                syn::GenericParam::Const(c) => c.default.is_some(),
+                syn::GenericParam::Lifetime(_) => false,
+            })
+            .then(|| quote!(= #parent_ident));
+
+        quote!(
+            #[doc = #error_doc]
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            #vis struct #error<#value #error_default> {
+                value: #value,
+                variant: core::option::Option<&'static str>,
+            }
+
+            #[allow(dead_code)]
+            impl<#value> #error<#value> {
+                fn new(value: #value, variant: &'static str) -> Self {
+                    Self { value, variant: core::option::Option::Some(variant) }
+                }
+
+                fn unknown(value: #value) -> Self {
+                    Self { value, variant: core::option::Option::None }
+                }
+
+                /// Returns the value that failed to convert.
+                #vis fn into_inner(self) -> #value {
+                    self.value
+                }
+
+                /// Returns a reference to the value that failed to convert.
+                #vis fn as_inner(&self) -> &#value {
+                    &self.value
+                }
+
+                #[doc = #variant_doc]
+                #vis fn variant(&self) -> core::option::Option<&'static str> {
+                    self.variant
+                }
+
+                #[doc = #parent_name_doc]
+                #vis fn parent_name(&self) -> &'static str {
+                    #parent_name
+                }
+
+                #[doc = #child_name_doc]
+                #vis fn child_name(&self) -> &'static str {
+                    #child_name
+                }
+
+                #[doc = #expected_doc]
+                #vis fn expected(&self) -> &'static [&'static str] {
+                    &[#(#expected),*]
+                }
+            }
+
+            impl<#value> core::fmt::Display for #error<#value> {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    match self.variant {
+                        core::option::Option::Some(variant) => core::write!(
+                            f,
+                            "{}::{} is not a variant of {} (expected one of: {})",
+                            #parent_name,
+                            variant,
+                            #child_name,
+                            #expected_list,
+                        ),
+                        core::option::Option::None => core::write!(
+                            f,
+                            "not a discriminant of {} (expected one of: {})",
+                            #parent_name,
+                            #expected_list,
+                        ),
+                    }
+                }
+            }
+
+            #error_trait_impl
+        )
+    }
+}
@@ -0,0 +1,70 @@
+use alloc::{format, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, Generics, Ident, Variant, Visibility};
+
+use super::parent_variants;
+use crate::r#enum::Enum;
+
+// Build `ALL`, `COUNT` and `index` for a fieldless enum, or nothing if any of
+// its variants has fields.
+fn build_index<'a>(
+    ident: &Ident,
+    generics: &Generics,
+    variants: impl IntoIterator<Item = &'a Variant>,
+    vis: &Visibility,
+) -> TokenStream2 {
+    let variants: Vec<&Ident> = variants
+        .into_iter()
+        .map(|v| (v.fields.is_empty()).then_some(&v.ident))
+        .collect::<Option<_>>()
+        .unwrap_or_default();
+    if variants.is_empty() {
+        return quote!();
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let count = variants.len();
+    let indices = 0..count;
+
+    let all_doc = format!("Every variant of [`{ident}`], in declaration order.");
+    let count_doc = format!("The number of variants of [`{ident}`].");
+    let index_doc = format!(
+        "The position of this variant in [`{ident}::ALL`], for indexing tables by [`{ident}`]."
+    );
+
+    quote!(
+        #[allow(dead_code)]
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #all_doc]
+            #vis const ALL: [Self; #count] = [#(Self::#variants),*];
+
+            #[doc = #count_doc]
+            #vis const COUNT: usize = #count;
+
+            #[doc = #index_doc]
+            #vis const fn index(self) -> usize {
+                match self {
+                    #(Self::#variants => #indices,)*
+                }
+            }
+        }
+    )
+}
+
+/// Build `ALL`, `COUNT` and `index` for the parent, if it's fieldless.
+pub fn build_parent_index(parent: &DeriveInput) -> TokenStream2 {
+    build_index(
+        &parent.ident,
+        &parent.generics,
+        parent_variants(parent),
+        &parent.vis,
+    )
+}
+
+impl Enum {
+    /// Build `ALL`, `COUNT` and `index`, if we're fieldless.
+    pub fn build_index(&self, parent: &DeriveInput) -> TokenStream2 {
+        build_index(&self.ident, &self.generics, &self.variants, &parent.vis)
+    }
+}
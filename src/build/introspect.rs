@@ -0,0 +1,109 @@
+use alloc::{format, string::ToString, vec::Vec};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use super::parent_variants;
+use crate::r#enum::Enum;
+
+/// Build `{Parent}Subenum`, with a variant naming each subenum, along with
+/// `{Parent}::SUBENUMS`, `subenums` and `belongs_to`, so which subenums a
+/// value is in can be found at runtime.
+pub fn build_introspection(parent: &DeriveInput, children: &[Enum]) -> TokenStream2 {
+    let parent_ident = &parent.ident;
+    let subenum_ident = format_ident!("{}Subenum", parent_ident);
+    let vis = &parent.vis;
+    let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
+
+    let idents: Vec<_> = children.iter().map(|e| &e.ident).collect();
+    let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+    let count = children.len();
+    let variant_docs = idents
+        .iter()
+        .map(|ident| format!("[`{ident}`], a subenum of [`{parent_ident}`]."));
+    let variant_lists = children.iter().map(|e| {
+        let variants = e.variants.iter().map(|v| v.ident.to_string());
+        quote!(&[#(#variants),*])
+    });
+    let is_fns = children
+        .iter()
+        .map(|e| format_ident!("is_{}", e.ident.to_string().to_snake_case()));
+
+    // Each of the parent's variants, with the names of the subenums it's in.
+    let (variant_names, memberships): (Vec<_>, Vec<_>) = parent_variants(parent)
+        .iter()
+        .map(|variant| {
+            let subenums = children
+                .iter()
+                .filter(|e| e.contains(&variant.ident))
+                .map(|e| e.ident.to_string());
+            (variant.ident.to_string(), quote!(&[#(#subenums),*]))
+        })
+        .unzip();
+    let variant_idents = parent_variants(parent).iter().map(|v| &v.ident);
+
+    let subenum_doc = format!("Names each subenum of [`{parent_ident}`].");
+    let all_doc = format!("Every subenum of [`{parent_ident}`], sorted by name.");
+    let name_doc = "The name of this subenum.";
+    let variants_doc = format!(
+        "The names of this subenum's variants, in the order they're declared in \
+        [`{parent_ident}`]."
+    );
+    let table_doc = format!(
+        "The name of each of [`{parent_ident}`]'s variants, with the names of the subenums \
+        it's in."
+    );
+    let subenums_doc = "The names of the subenums this is in, sorted by name.";
+    let belongs_to_doc = "Returns whether this is in the subenum `subenum`.";
+
+    quote!(
+        #[doc = #subenum_doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #vis enum #subenum_ident {
+            #(#[doc = #variant_docs] #idents,)*
+        }
+
+        #[allow(dead_code)]
+        impl #subenum_ident {
+            #[doc = #all_doc]
+            #vis const ALL: [Self; #count] = [#(Self::#idents),*];
+
+            #[doc = #name_doc]
+            #vis const fn name(self) -> &'static str {
+                match self {
+                    #(Self::#idents => #names,)*
+                }
+            }
+
+            #[doc = #variants_doc]
+            #vis const fn variants(self) -> &'static [&'static str] {
+                match self {
+                    #(Self::#idents => #variant_lists,)*
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #parent_impl #parent_ident #parent_ty #parent_where {
+            #[doc = #table_doc]
+            #vis const SUBENUMS: &'static [(&'static str, &'static [&'static str])] = &[
+                #((#variant_names, #memberships),)*
+            ];
+
+            #[doc = #subenums_doc]
+            #vis fn subenums(&self) -> &'static [&'static str] {
+                match self {
+                    #(#parent_ident::#variant_idents { .. } => #memberships,)*
+                }
+            }
+
+            #[doc = #belongs_to_doc]
+            #vis fn belongs_to(&self, subenum: #subenum_ident) -> bool {
+                match subenum {
+                    #(#subenum_ident::#idents => self.#is_fns(),)*
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,101 @@
+use alloc::{format, string::ToString};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_quote, DeriveInput, Ident};
+
+use crate::r#enum::Enum;
+
+fn iter_param() -> Ident {
+    parse_quote!(SubenumIter)
+}
+
+/// Build `{Parent}IterExt`, an extension trait for iterators of the parent
+/// with methods to narrow them to each subenum.
+pub fn build_iter_ext(parent: &DeriveInput, children: &[Enum]) -> TokenStream2 {
+    let parent_ident = &parent.ident;
+    let trait_ident = format_ident!("{}IterExt", parent_ident);
+    let iter = iter_param();
+    let vis = &parent.vis;
+
+    let (_, parent_ty, parent_where) = parent.generics.split_for_impl();
+    let parent_generics = &parent.generics;
+
+    let mut impl_generics = parent.generics.clone();
+    impl_generics.params.push(parse_quote!(#iter));
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#iter: core::iter::Iterator<Item = #parent_ident #parent_ty>));
+    let (impl_generics, _, impl_where) = impl_generics.split_for_impl();
+
+    let methods = children.iter().map(|child| {
+        let child_ident = &child.ident;
+        let (_, child_ty, _) = child.generics.split_for_impl();
+        let snake = child.ident.to_string().to_snake_case();
+        let into_fn = format_ident!("into_{snake}");
+        let filter_fn = format_ident!("filter_{snake}");
+        let map_while_fn = format_ident!("map_while_{snake}");
+        let partition_fn = format_ident!("partition_{snake}");
+
+        let filter_doc = format!(
+            "Keeps only the [`{child_ident}`]s, converting each with `TryFrom` and skipping the \
+            rest."
+        );
+        let map_while_doc = format!(
+            "Converts values to [`{child_ident}`] with `TryFrom` until one isn't one of its \
+            variants."
+        );
+        let partition_doc = format!(
+            "Splits this into the [`{child_ident}`]s and the remaining [`{parent_ident}`]s, like \
+            [`Iterator::partition`]."
+        );
+
+        let narrow =
+            quote!(fn(#parent_ident #parent_ty) -> core::option::Option<#child_ident #child_ty>);
+
+        quote!(
+            #[doc = #filter_doc]
+            fn #filter_fn(self) -> core::iter::FilterMap<Self, #narrow> {
+                self.filter_map(|value| core::convert::TryFrom::try_from(value).ok())
+            }
+
+            #[doc = #map_while_doc]
+            fn #map_while_fn(self) -> core::iter::MapWhile<Self, #narrow> {
+                self.map_while(|value| core::convert::TryFrom::try_from(value).ok())
+            }
+
+            #[doc = #partition_doc]
+            fn #partition_fn<Accepted, Rejected>(self) -> (Accepted, Rejected)
+            where
+                Accepted: core::default::Default + core::iter::Extend<#child_ident #child_ty>,
+                Rejected: core::default::Default + core::iter::Extend<#parent_ident #parent_ty>,
+            {
+                let mut accepted = Accepted::default();
+                let mut rejected = Rejected::default();
+                for value in self {
+                    match value.#into_fn() {
+                        Ok(child) => accepted.extend(core::iter::once(child)),
+                        Err(value) => rejected.extend(core::iter::once(value)),
+                    }
+                }
+                (accepted, rejected)
+            }
+        )
+    });
+
+    let trait_doc = format!(
+        "Methods for narrowing iterators of [`{parent_ident}`] to its subenums.\n\n\
+        This is implemented for every such iterator."
+    );
+
+    quote!(
+        #[doc = #trait_doc]
+        #vis trait #trait_ident #parent_generics: core::iter::Iterator<Item = #parent_ident #parent_ty> + core::marker::Sized #parent_where {
+            #(#methods)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #parent_ty for #iter #impl_where {}
+    )
+}
@@ -0,0 +1,169 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use heck::ToShoutySnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, DeriveInput, Ident, Token, Variant};
+
+use super::{
+    build_conversion, extra_generics, parent_variants,
+    repr::{parent_discriminants, repr_hints, repr_type, INT_REPRS},
+    views::{view_lifetime, with_view_lifetime},
+};
+use crate::r#enum::{Discriminants, Enum};
+
+// Whether `hints` give an enum a defined layout: `C`, an integer, or both.
+fn has_defined_layout(hints: &[Ident]) -> bool {
+    hints
+        .iter()
+        .any(|hint| hint == "C" || INT_REPRS.iter().any(|repr| hint == repr))
+}
+
+// `hints` in a canonical order, to compare them.
+fn sorted(hints: &[Ident]) -> Vec<String> {
+    let mut hints: Vec<String> = hints.iter().map(Ident::to_string).collect();
+    hints.sort_unstable();
+    hints
+}
+
+impl Enum {
+    pub fn layout_check_ident(&self) -> Ident {
+        format_ident!(
+            "{}_IS_LAYOUT_COMPATIBLE",
+            self.ident.to_string().to_shouty_snake_case()
+        )
+    }
+
+    /// Our variants, with the parent's discriminants made explicit if we're
+    /// `layout_compatible`, so each of our variants has the same tag as the
+    /// parent's.
+    pub fn layout_variants(&self, parent: &DeriveInput) -> Punctuated<Variant, Token![,]> {
+        if !self.layout_compatible {
+            return self.variants.clone();
+        }
+        let parent_hints = repr_hints(&parent.attrs);
+        if !has_defined_layout(&parent_hints) {
+            panic!(
+                "`{}` can only be `layout_compatible` if `{}` has a `#[repr(C)]` or integer `repr`",
+                self.ident, parent.ident
+            );
+        }
+        // Only enums with an integer `repr` can give variants with fields
+        // explicit discriminants, which we need to match the parent's.
+        if repr_type(parent).is_none()
+            && parent_variants(parent).iter().any(|v| !v.fields.is_empty())
+        {
+            panic!(
+                "`{}` can only be `layout_compatible` if `{}`, whose variants have fields, has an \
+                integer `repr`, like `#[repr(C, u8)]`, rather than just `#[repr(C)]`",
+                self.ident, parent.ident
+            );
+        }
+        if sorted(&self.repr) != sorted(&parent_hints) {
+            panic!(
+                "`{}` can only be `layout_compatible` if it has the same `repr` as `{}`, so it \
+                mustn't be given only to either of them",
+                self.ident, parent.ident
+            );
+        }
+        if self.discriminants != Discriminants::Inherit {
+            panic!(
+                "`{}` can only be `layout_compatible` with `discriminants = inherit`",
+                self.ident
+            );
+        }
+
+        let discriminants = parent_discriminants(parent);
+        let mut variants = self.variants.clone();
+        for variant in &mut variants {
+            let index = parent_variants(parent)
+                .iter()
+                .position(|v| v.ident == variant.ident)
+                .unwrap();
+            let discriminant = &discriminants[index];
+            variant.discriminant = Some((Default::default(), syn::parse_quote!(#discriminant)));
+        }
+        variants
+    }
+
+    /// Build `as_parent` and `TryFrom<&Parent> for &Child`, which cast
+    /// between references, along with the assertions that make them sound.
+    pub fn build_layout_casts(&self, parent: &DeriveInput) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let error = self.error_ident();
+        let lifetime = view_lifetime();
+        let vis = &parent.vis;
+        let check = self.layout_check_ident();
+
+        let (child_impl, child_ty, child_where) = self.generics.split_for_impl();
+        let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
+        let extra = extra_generics(&self.generics, &parent.generics);
+        let extra_where = &extra.where_clause;
+
+        // Our variants have the same discriminants and fields as the
+        // parent's, and with a `repr` giving both a defined layout, those
+        // variants are laid out the same. If the enums are also the same size
+        // and alignment, a reference to one of our values is a valid
+        // reference to the parent, and vice versa for the parent's values
+        // that are our variants.
+        let check_message = format!(
+            "`{child_ident}` must have the same size and alignment as `{parent_ident}` to be \
+            `layout_compatible`"
+        );
+        let as_parent_doc = format!(
+            "Borrows this as a [`{parent_ident}`], without copying it, as their layouts are \
+            compatible."
+        );
+
+        let variant_idents = self.variants.iter().map(|v| &v.ident);
+        let accepted = quote!(#(#parent_ident::#variant_idents { .. })|*);
+        let rejected_arms =
+            self.rejected_arms(parent, |variant| quote!(Err(#error::new(value, #variant))));
+        let from_ref = build_conversion(
+            &with_view_lifetime(&parent.generics),
+            quote!(&#lifetime #parent_ident #parent_ty),
+            quote!(&#lifetime #child_ident #child_ty),
+            quote!(#error<&#lifetime #parent_ident #parent_ty>),
+            [(
+                accepted,
+                quote!({
+                    let () = <#parent_ident #parent_ty>::#check;
+                    // SAFETY: `value` is one of our variants, and our
+                    // layouts are compatible.
+                    unsafe { &*(value as *const #parent_ident #parent_ty as *const #child_ident #child_ty) }
+                }),
+            )],
+            &rejected_arms,
+        );
+
+        quote!(
+            #[allow(dead_code)]
+            impl #parent_impl #parent_ident #parent_ty #parent_where {
+                const #check: () = {
+                    core::assert!(
+                        core::mem::size_of::<Self>() == core::mem::size_of::<#child_ident #child_ty>()
+                            && core::mem::align_of::<Self>() == core::mem::align_of::<#child_ident #child_ty>(),
+                        #check_message,
+                    );
+                };
+            }
+
+            #[allow(dead_code)]
+            impl #child_impl #child_ident #child_ty #child_where {
+                #[doc = #as_parent_doc]
+                #vis fn as_parent #extra(&self) -> &#parent_ident #parent_ty #extra_where {
+                    let () = <#parent_ident #parent_ty>::#check;
+                    // SAFETY: Our layouts are compatible, and each of our
+                    // variants is one of the parent's.
+                    unsafe { &*(self as *const Self as *const #parent_ident #parent_ty) }
+                }
+            }
+
+            #from_ref
+        )
+    }
+}
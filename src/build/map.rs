@@ -0,0 +1,177 @@
+use alloc::{format, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Attribute, DeriveInput, Ident, Visibility};
+
+use super::{fieldless_variants, parent_variants};
+use crate::{derive::derives_trait, r#enum::Enum};
+
+pub fn map_ident(ident: &Ident) -> Ident {
+    format_ident!("{}Map", ident)
+}
+
+// Build `{ident}Map<V>`, holding a value for each variant of the fieldless enum
+// `ident` in an array, at the variant's `index`.
+fn build_map(
+    ident: &Ident,
+    variants: &[&Ident],
+    vis: &Visibility,
+    derive_debug: bool,
+) -> TokenStream2 {
+    let map_ident = map_ident(ident);
+    let indices = 0..variants.len();
+
+    let map_doc = format!(
+        "A map with a value for every [`{ident}`], stored in an array indexed by\n\
+        [`{ident}::index`]."
+    );
+    let from_fn_doc = format!("Creates a map with the value `f` returns for each [`{ident}`].");
+
+    let debug_impl = derive_debug.then(|| {
+        quote!(
+            impl<V: core::fmt::Debug> core::fmt::Debug for #map_ident<V> {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.debug_map().entries(self.iter()).finish()
+                }
+            }
+        )
+    });
+
+    quote!(
+        #[doc = #map_doc]
+        #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+        #vis struct #map_ident<V> {
+            values: [V; #ident::COUNT],
+        }
+
+        #[allow(dead_code)]
+        impl<V> #map_ident<V> {
+            // The key at `index`, the inverse of `index`.
+            fn key(index: usize) -> #ident {
+                match index {
+                    #(#indices => #ident::#variants,)*
+                    _ => core::unreachable!(),
+                }
+            }
+
+            #[doc = #from_fn_doc]
+            #vis fn from_fn(mut f: impl core::ops::FnMut(#ident) -> V) -> Self {
+                Self {
+                    values: core::array::from_fn(|index| f(Self::key(index))),
+                }
+            }
+
+            /// Returns a reference to the value for `key`.
+            #vis fn get(&self, key: #ident) -> &V {
+                &self.values[key.index()]
+            }
+
+            /// Returns a mutable reference to the value for `key`.
+            #vis fn get_mut(&mut self, key: #ident) -> &mut V {
+                &mut self.values[key.index()]
+            }
+
+            /// Returns an iterator over the keys and their values, in the
+            /// order the keys are declared.
+            #vis fn iter(&self) -> impl core::iter::Iterator<Item = (#ident, &V)> {
+                self.values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| (Self::key(index), value))
+            }
+
+            /// Returns an iterator over the keys and mutable references to
+            /// their values, in the order the keys are declared.
+            #vis fn iter_mut(&mut self) -> impl core::iter::Iterator<Item = (#ident, &mut V)> {
+                self.values
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(index, value)| (Self::key(index), value))
+            }
+
+            /// Returns the values, in the order their keys are declared.
+            #vis fn into_values(self) -> [V; #ident::COUNT] {
+                self.values
+            }
+        }
+
+        #debug_impl
+
+        #[automatically_derived]
+        impl<V> core::ops::Index<#ident> for #map_ident<V> {
+            type Output = V;
+
+            fn index(&self, key: #ident) -> &V {
+                self.get(key)
+            }
+        }
+
+        #[automatically_derived]
+        impl<V> core::ops::IndexMut<#ident> for #map_ident<V> {
+            fn index_mut(&mut self, key: #ident) -> &mut V {
+                self.get_mut(key)
+            }
+        }
+    )
+}
+
+/// Build the parent's `{Parent}Map`, along with projections from it to each
+/// subenum's map.
+pub fn build_parent_map(parent: &DeriveInput, children: &[Enum]) -> TokenStream2 {
+    let parent_ident = &parent.ident;
+    let parent_map = map_ident(parent_ident);
+    let variants = fieldless_variants(parent_ident, parent_variants(parent), "map");
+    let map = build_map(
+        parent_ident,
+        &variants,
+        &parent.vis,
+        derives_trait(&parent.attrs, "Debug"),
+    );
+
+    let projections = children.iter().filter(|child| child.map).map(|child| {
+        let child_ident = &child.ident;
+        let child_map = map_ident(child_ident);
+
+        // Where each of the child's values is in the parent's map.
+        let parent_indices: Vec<usize> = child
+            .variants
+            .iter()
+            .map(|variant| variants.iter().position(|v| *v == &variant.ident).unwrap())
+            .collect();
+
+        quote!(
+            #[automatically_derived]
+            impl<V> core::convert::From<#parent_map<V>> for #child_map<V> {
+                fn from(map: #parent_map<V>) -> Self {
+                    const PARENT_INDICES: [usize; #child_ident::COUNT] = [#(#parent_indices),*];
+
+                    let mut values = map.values.map(core::option::Option::Some);
+                    Self {
+                        values: core::array::from_fn(|index| {
+                            values[PARENT_INDICES[index]].take().unwrap()
+                        }),
+                    }
+                }
+            }
+        )
+    });
+
+    quote!(
+        #map
+
+        #(#projections)*
+    )
+}
+
+impl Enum {
+    /// Build `{Child}Map<V>`, a map keyed by this enum.
+    pub fn build_map(&self, parent: &DeriveInput, child_attrs: &[Attribute]) -> TokenStream2 {
+        let variants = fieldless_variants(&self.ident, &self.variants, "map");
+        build_map(
+            &self.ident,
+            &variants,
+            &parent.vis,
+            derives_trait(child_attrs, "Debug") || derives_trait(&self.attributes, "Debug"),
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::{format, string::ToString};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Ident, Visibility};
+
+use crate::r#enum::Enum;
+
+// A name for the `#[macro_export]`ed macro behind a public `{child}_pat!`.
+// Exported macros all live at the crate root, so this includes a hash of the
+// parent's declaration, to keep those of same-named subenums of different
+// parents of the same name apart.
+fn exported_name(parent: &DeriveInput, name: &Ident) -> Ident {
+    let hash = quote!(#parent)
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format_ident!("__subenum_{}_{}_{:016x}", parent.ident, name, hash)
+}
+
+impl Enum {
+    /// Build the `{child}_pat!` macro, which expands to a pattern matching any
+    /// of this enum's variants on the parent, or to a `match` whose first arm
+    /// binds such a value narrowed to this enum.
+    pub fn build_pattern_macro(&self, parent: &DeriveInput) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let snake = self.ident.to_string().to_snake_case();
+        let name = format_ident!("{snake}_pat");
+        let into_fn = format_ident!("into_{snake}");
+
+        let variant_idents = self.variants.iter().map(|v| &v.ident);
+        let pattern = quote!(#(#parent_ident::#variant_idents { .. })|*);
+
+        let doc = format!(
+            "Expands to a pattern matching any of [`{child_ident}`]'s variants on a \
+            [`{parent_ident}`], which must be in scope where it's used.\n\n\
+            `{name}!()` matches without binding anything, while `{name}!(x)` binds the \
+            matched [`{parent_ident}`] to `x`. As a pattern can't change the type of what it \
+            binds, `{name}!(value, x => arm, ...)` instead expands to a `match` on `value`, \
+            with `x` bound to the matched [`{child_ident}`] in its first arm, followed by the \
+            rest of the given arms."
+        );
+
+        let rules = quote!(
+            () => {
+                #pattern
+            };
+            ($binding:ident) => {
+                $binding @ (#pattern)
+            };
+            ($value:expr, $binding:ident => $arm:expr $(, $($rest:tt)*)?) => {
+                match $value {
+                    $binding @ (#pattern) => {
+                        let $binding = match #parent_ident::#into_fn($binding) {
+                            core::result::Result::Ok(child) => child,
+                            core::result::Result::Err(_) => core::unreachable!(),
+                        };
+                        $arm
+                    }
+                    $($($rest)*)?
+                }
+            };
+        );
+
+        // `macro_rules!` macros aren't items, so we give them the child's
+        // visibility by re-exporting them. Only `#[macro_export]`ed ones can be
+        // public, and those are placed at the crate root, where those of
+        // identical enums in different modules would clash, so public children
+        // only export theirs, under a hidden name, with `pat`. Otherwise,
+        // their macros are crate-visible.
+        match &parent.vis {
+            Visibility::Public(_) if self.pat => {
+                let exported = exported_name(parent, &name);
+                quote!(
+                    #[doc(hidden)]
+                    #[macro_export]
+                    macro_rules! #exported {
+                        #rules
+                    }
+
+                    #[doc = #doc]
+                    pub use #exported as #name;
+                )
+            }
+            vis => {
+                let vis = match vis {
+                    Visibility::Public(_) => quote!(pub(crate)),
+                    vis => quote!(#vis),
+                };
+                quote!(
+                    #[doc = #doc]
+                    #[allow(unused_macros)]
+                    macro_rules! #name {
+                        #rules
+                    }
+
+                    #[allow(unused_imports)]
+                    #vis use #name;
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use alloc::{string::ToString, vec::Vec};
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, Attribute, DeriveInput, Ident, Token};
+
+use super::{parent_variants, runs::rejected};
+use crate::r#enum::Enum;
+
+pub const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// The hints in the `#[repr(..)]` attributes among `attrs`, like `C` and `u8`.
+pub fn repr_hints<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Vec<Ident> {
+    attrs
+        .into_iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+// The integer type among `hints`, if there is one.
+fn int_repr(hints: &[Ident]) -> Option<Ident> {
+    hints
+        .iter()
+        .find(|ident| INT_REPRS.iter().any(|repr| *ident == repr))
+        .cloned()
+}
+
+/// The integer type in the parent's `#[repr(..)]`, if it has one.
+pub fn repr_type(parent: &DeriveInput) -> Option<Ident> {
+    int_repr(&repr_hints(&parent.attrs))
+}
+
+/// The discriminant of each of the parent's variants, as an expression. Those
+/// without an explicit one are one more than the previous variant's, as for
+/// the parent itself.
+pub fn parent_discriminants(parent: &DeriveInput) -> Vec<TokenStream2> {
+    let mut last_explicit = None;
+    let mut offset = 0u128;
+
+    parent_variants(parent)
+        .iter()
+        .map(|variant| {
+            if let Some((_, expr)) = &variant.discriminant {
+                last_explicit = Some(expr);
+                offset = 0;
+            }
+            let offset_lit = Literal::u128_unsuffixed(offset);
+            let discriminant = match last_explicit {
+                Some(expr) if offset == 0 => quote!(#expr),
+                Some(expr) => quote!((#expr) + #offset_lit),
+                None => quote!(#offset_lit),
+            };
+            offset += 1;
+            discriminant
+        })
+        .collect()
+}
+
+impl Enum {
+    /// The integer type in our own `#[repr(..)]`, if we have one. That's the
+    /// parent's, unless it was given to the parent alone, or we were given
+    /// another.
+    pub fn repr_type(&self) -> Option<Ident> {
+        int_repr(&self.repr)
+    }
+
+    /// Build conversions between a fieldless child and the parent's `repr`
+    /// type, using the parent's discriminants.
+    pub fn build_repr_conversions(&self, parent: &DeriveInput) -> TokenStream2 {
+        let Some(repr) = repr_type(parent) else {
+            return quote!();
+        };
+        if self.variants.iter().any(|v| !v.fields.is_empty()) {
+            return quote!();
+        }
+
+        let child_ident = &self.ident;
+        let error = self.error_ident();
+        let (child_impl, child_ty, child_where) = self.generics.split_for_impl();
+
+        if let (Some(from_parent), Some(to_parent)) =
+            (self.runs_from_parent(parent), self.runs_to_parent(parent))
+        {
+            let unreachable = quote!(unsafe { core::hint::unreachable_unchecked() });
+            let to_repr = to_parent.map(to_parent.read(quote!(&value)), unreachable);
+            let child_value =
+                from_parent.convert(quote!(value), quote!(Self), rejected(parent, &error));
+            return quote!(
+                #[automatically_derived]
+                impl core::convert::From<#child_ident> for #repr {
+                    fn from(value: #child_ident) -> Self {
+                        #to_repr
+                    }
+                }
+
+                #[automatically_derived]
+                impl core::convert::TryFrom<#repr> for #child_ident {
+                    type Error = #error<#repr>;
+
+                    fn try_from(value: #repr) -> core::result::Result<Self, <Self as core::convert::TryFrom<#repr>>::Error> {
+                        Ok(#child_value)
+                    }
+                }
+            );
+        }
+
+        let discriminants = parent_discriminants(parent);
+        let variants = parent_variants(parent);
+        // Discriminants can be arbitrary expressions, so we name them with
+        // consts to match against. They're prefixed so as not to shadow any
+        // consts the discriminants themselves are named by.
+        let consts: Vec<Ident> = variants
+            .iter()
+            .map(|v| format_ident!("__SUBENUM_{}", v.ident.to_string().to_shouty_snake_case()))
+            .collect();
+
+        let mut to_repr_arms = Vec::new();
+        let mut from_repr_arms = Vec::new();
+        for ((variant, discriminant), name) in variants.iter().zip(&discriminants).zip(&consts) {
+            let ident = &variant.ident;
+            if self.contains(ident) {
+                to_repr_arms.push(quote!(#child_ident::#ident => #discriminant));
+                from_repr_arms.push(quote!(#name => Ok(#child_ident::#ident)));
+            } else {
+                let variant = ident.to_string();
+                from_repr_arms.push(quote!(#name => Err(#error::new(value, #variant))));
+            }
+        }
+
+        quote!(
+            #[automatically_derived]
+            impl #child_impl core::convert::From<#child_ident #child_ty> for #repr #child_where {
+                fn from(value: #child_ident #child_ty) -> Self {
+                    match value {
+                        #(#to_repr_arms,)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #child_impl core::convert::TryFrom<#repr> for #child_ident #child_ty #child_where {
+                type Error = #error<#repr>;
+
+                fn try_from(value: #repr) -> core::result::Result<Self, <Self as core::convert::TryFrom<#repr>>::Error> {
+                    #(const #consts: #repr = #discriminants;)*
+
+                    match value {
+                        #(#from_repr_arms,)*
+                        _ => Err(#error::unknown(value)),
+                    }
+                }
+            }
+        )
+    }
+}
@@ -0,0 +1,340 @@
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{BinOp, DeriveInput, Expr, ExprBinary, ExprLit, ExprUnary, Ident, Lit, UnOp, Variant};
+
+use super::{parent_variants, repr::repr_type};
+use crate::r#enum::Enum;
+
+// The value of a discriminant written as an integer literal, possibly
+// negated, or one offset from it like those of `parent_discriminants`.
+fn literal_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::Add(_),
+            right,
+            ..
+        }) => literal_value(left)?.checked_add(literal_value(right)?),
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_value(expr).map(|value| -value),
+        Expr::Group(group) => literal_value(&group.expr),
+        Expr::Paren(paren) => literal_value(&paren.expr),
+        _ => None,
+    }
+}
+
+// The discriminant of each of `variants`, by name, if they're all fieldless
+// and any explicit ones are literals. Those without one are one more than the
+// previous variant's, as for the enum itself.
+fn literal_discriminants<'a>(
+    variants: impl IntoIterator<Item = &'a Variant>,
+) -> Option<BTreeMap<Ident, i128>> {
+    let mut next = 0;
+    variants
+        .into_iter()
+        .map(|variant| {
+            if !variant.fields.is_empty() {
+                return None;
+            }
+            let value = match &variant.discriminant {
+                Some((_, expr)) => literal_value(expr)?,
+                None => next,
+            };
+            next = value.checked_add(1)?;
+            Some((variant.ident.clone(), value))
+        })
+        .collect()
+}
+
+// The parent's `repr` type and discriminants, if it's fieldless and each of
+// them is known here.
+fn parent_discriminants(parent: &DeriveInput) -> Option<(Ident, BTreeMap<Ident, i128>)> {
+    let repr = repr_type(parent)?;
+    if !parent.generics.params.is_empty() || parent_variants(parent).is_empty() {
+        return None;
+    }
+    Some((repr, literal_discriminants(parent_variants(parent))?))
+}
+
+/// How the discriminants of one fieldless enum map onto another's, as runs
+/// of consecutive discriminants that map onto consecutive discriminants. Both
+/// enums have the same integer `repr`.
+/// Converting with these needs one match arm per run, rather than one per
+/// variant, which keeps huge enums quick to compile.
+pub struct Runs {
+    repr: Ident,
+    // The first and last discriminant of each run, and the discriminant the
+    // first maps to.
+    runs: Vec<(i128, i128, i128)>,
+}
+
+impl Runs {
+    // Map the discriminants in `from` onto those of the same variants in `to`.
+    fn between(repr: Ident, from: &BTreeMap<Ident, i128>, to: &BTreeMap<Ident, i128>) -> Self {
+        let mut pairs: Vec<(i128, i128)> = from
+            .iter()
+            .filter_map(|(ident, &from)| Some((from, *to.get(ident)?)))
+            .collect();
+        pairs.sort_unstable();
+
+        let mut runs: Vec<(i128, i128, i128)> = Vec::new();
+        for (from, to) in pairs {
+            match runs.last_mut() {
+                Some((first, last, target))
+                    if *last + 1 == from && to - *target == from - *first =>
+                {
+                    *last = from;
+                }
+                _ => runs.push((from, from, to)),
+            }
+        }
+        Runs { repr, runs }
+    }
+
+    /// The discriminant of `value`, a reference to a fieldless enum with our
+    /// `repr`.
+    pub fn read(&self, value: TokenStream2) -> TokenStream2 {
+        let repr = &self.repr;
+        // SAFETY: The enum is fieldless with a primitive representation, so
+        // it's just its discriminant.
+        quote!(unsafe { *(#value as *const _ as *const #repr) })
+    }
+
+    /// A pattern matching the discriminants we map from.
+    pub fn pattern(&self) -> TokenStream2 {
+        let pats = self.runs.iter().map(|&(first, last, _)| {
+            let first_lit = Literal::i128_unsuffixed(first);
+            let last_lit = Literal::i128_unsuffixed(last);
+            if first == last {
+                quote!(#first_lit)
+            } else {
+                quote!(#first_lit..=#last_lit)
+            }
+        });
+        quote!(#(#pats)|*)
+    }
+
+    /// The discriminant that `discriminant` maps to, or `fallback`, with
+    /// `discriminant` bound to it, if it isn't in any of our runs.
+    pub fn map(&self, discriminant: TokenStream2, fallback: TokenStream2) -> TokenStream2 {
+        let arms = self.runs.iter().map(|&(first, last, target)| {
+            let first_lit = Literal::i128_unsuffixed(first);
+            let last_lit = Literal::i128_unsuffixed(last);
+            let pat = if first == last {
+                quote!(#first_lit)
+            } else {
+                quote!(#first_lit..=#last_lit)
+            };
+            let target_lit = Literal::i128_unsuffixed(target);
+            let value = if first == target {
+                quote!(discriminant)
+            } else {
+                quote!(discriminant.wrapping_sub(#first_lit).wrapping_add(#target_lit))
+            };
+            quote!(discriminant @ #pat => #value)
+        });
+        quote!(
+            match #discriminant {
+                #(#arms,)*
+                #[allow(unused_variables)]
+                discriminant => #fallback,
+            }
+        )
+    }
+
+    /// The value of `ty`, a fieldless enum with our `repr`, that
+    /// `discriminant` maps to, or `fallback` as for [`Runs::map`].
+    pub fn convert(
+        &self,
+        discriminant: TokenStream2,
+        ty: TokenStream2,
+        fallback: TokenStream2,
+    ) -> TokenStream2 {
+        let repr = &self.repr;
+        let discriminant = self.map(discriminant, fallback);
+        quote!({
+            let discriminant: #repr = #discriminant;
+            // SAFETY: Each of our runs maps onto discriminants of `ty`.
+            unsafe { core::mem::transmute::<#repr, #ty>(discriminant) }
+        })
+    }
+}
+
+/// Build `subenum_variant_name`, which finds the name of the parent's variant
+/// with a given discriminant, for conversions using [`Runs`] to report
+/// rejected variants with.
+pub fn build_parent_variant_name(parent: &DeriveInput) -> TokenStream2 {
+    let Some((repr, discriminants)) = parent_discriminants(parent) else {
+        return quote!();
+    };
+    let parent_ident = &parent.ident;
+    let count = discriminants.len();
+    let names = discriminants.keys().map(Ident::to_string);
+    let discriminants = discriminants.values().map(|&d| Literal::i128_unsuffixed(d));
+
+    quote!(
+        #[allow(dead_code)]
+        impl #parent_ident {
+            fn subenum_variant_name(discriminant: #repr) -> core::option::Option<&'static str> {
+                const NAMES: [(#repr, &str); #count] = [#((#discriminants, #names)),*];
+
+                NAMES
+                    .iter()
+                    .find(|(d, _)| *d == discriminant)
+                    .map(|(_, name)| *name)
+            }
+        }
+    )
+}
+
+/// An expression returning the conversion error for `value`, given the
+/// parent's discriminant for it as `discriminant`.
+pub fn rejected(parent: &DeriveInput, error: &Ident) -> TokenStream2 {
+    let parent_ident = &parent.ident;
+    quote!(
+        return Err(match #parent_ident::subenum_variant_name(discriminant) {
+            Some(name) => #error::new(value, name),
+            None => #error::unknown(value),
+        })
+    )
+}
+
+impl Enum {
+    // Our discriminants, by variant, if they and the parent's are all known
+    // here. We must have the parent's `repr`, too, to read ours as it.
+    fn discriminants(&self, parent: &DeriveInput) -> Option<(Ident, BTreeMap<Ident, i128>)> {
+        let (repr, _) = parent_discriminants(parent)?;
+        if self.repr_type().as_ref() != Some(&repr) {
+            return None;
+        }
+        Some((repr, literal_discriminants(&self.layout_variants(parent))?))
+    }
+
+    /// How the parent's discriminants map onto ours, if we're both fieldless
+    /// with discriminants known here.
+    pub fn runs_from_parent(&self, parent: &DeriveInput) -> Option<Runs> {
+        let (repr, from) = parent_discriminants(parent)?;
+        let (_, to) = self.discriminants(parent)?;
+        Some(Runs::between(repr, &from, &to))
+    }
+
+    /// How our discriminants map onto the parent's, as for
+    /// [`Enum::runs_from_parent`].
+    pub fn runs_to_parent(&self, parent: &DeriveInput) -> Option<Runs> {
+        let (repr, to) = parent_discriminants(parent)?;
+        let (_, from) = self.discriminants(parent)?;
+        Some(Runs::between(repr, &from, &to))
+    }
+
+    /// How our discriminants map onto those of our sibling `other`, for the
+    /// variants we share.
+    pub fn runs_to(&self, other: &Enum, parent: &DeriveInput) -> Option<Runs> {
+        let (repr, from) = self.discriminants(parent)?;
+        let (_, to) = other.discriminants(parent)?;
+        Some(Runs::between(repr, &from, &to))
+    }
+
+    /// Build `From<Self> for Parent` and `From` or `TryFrom<Parent>` for
+    /// ourselves by converting discriminants, if we can. With a complement,
+    /// it needs to be able to, too.
+    pub fn build_runs_conversions(
+        &self,
+        parent: &DeriveInput,
+        complement: Option<&Enum>,
+    ) -> Option<TokenStream2> {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let error = self.error_ident();
+        let unreachable = quote!(unsafe { core::hint::unreachable_unchecked() });
+
+        let from_parent = self.runs_from_parent(parent)?;
+        let to_parent = self.runs_to_parent(parent)?;
+        let read_value = from_parent.read(quote!(&value));
+
+        let parent_value = to_parent.convert(
+            to_parent.read(quote!(&child)),
+            quote!(#parent_ident),
+            unreachable.clone(),
+        );
+
+        let rejected = match complement {
+            Some(complement) => {
+                let complement_ident = &complement.ident;
+                let complement_value = complement.runs_from_parent(parent)?.convert(
+                    quote!(discriminant),
+                    quote!(#complement_ident),
+                    unreachable.clone(),
+                );
+                Some((
+                    quote!(#complement_ident),
+                    quote!(return Err(#complement_value)),
+                ))
+            }
+            None => (parent_variants(parent).len() != self.variants.len())
+                .then(|| (quote!(#error<#parent_ident>), rejected(parent, &error))),
+        };
+
+        let from_parent = match rejected {
+            Some((error, rejected)) => {
+                let child_value = from_parent.convert(read_value, quote!(Self), rejected);
+                quote!(
+                    #[automatically_derived]
+                    impl core::convert::TryFrom<#parent_ident> for #child_ident {
+                        type Error = #error;
+
+                        fn try_from(value: #parent_ident) -> core::result::Result<Self, <Self as core::convert::TryFrom<#parent_ident>>::Error> {
+                            Ok(#child_value)
+                        }
+                    }
+                )
+            }
+            None => {
+                let child_value = from_parent.convert(read_value, quote!(Self), unreachable);
+                quote!(
+                    #[automatically_derived]
+                    impl core::convert::From<#parent_ident> for #child_ident {
+                        fn from(value: #parent_ident) -> Self {
+                            #child_value
+                        }
+                    }
+                )
+            }
+        };
+
+        Some(quote!(
+            #[automatically_derived]
+            impl core::convert::From<#child_ident> for #parent_ident {
+                fn from(child: #child_ident) -> Self {
+                    #parent_value
+                }
+            }
+
+            #from_parent
+        ))
+    }
+
+    /// Build `PartialEq` between ourselves and `other`, which may be the
+    /// parent or a sibling, with `runs` from us to it, comparing
+    /// discriminants.
+    pub fn build_runs_partial_eq(&self, other: &Ident, runs: &Runs) -> TokenStream2 {
+        let ident = &self.ident;
+        let discriminant = runs.map(runs.read(quote!(self)), quote!(return false));
+        let other_discriminant = runs.read(quote!(other));
+
+        quote!(
+            #[automatically_derived]
+            impl PartialEq<#other> for #ident {
+                fn eq(&self, other: &#other) -> bool {
+                    (#discriminant) == #other_discriminant
+                }
+            }
+        )
+    }
+}
@@ -0,0 +1,302 @@
+use alloc::{format, string::ToString, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Attribute, DeriveInput, Ident, Visibility};
+
+use super::{fieldless_variants, parent_variants};
+use crate::{derive::derives_trait, r#enum::Enum};
+
+pub fn set_ident(ident: &Ident) -> Ident {
+    format_ident!("{}Set", ident)
+}
+
+// The smallest unsigned integer with a bit for each of `count` variants.
+fn bits_type(ident: &Ident, count: usize) -> Ident {
+    match count {
+        0..=8 => format_ident!("u8"),
+        9..=16 => format_ident!("u16"),
+        17..=32 => format_ident!("u32"),
+        33..=64 => format_ident!("u64"),
+        65..=128 => format_ident!("u128"),
+        _ => panic!("`{ident}` has more than 128 variants, which is too many for `set`"),
+    }
+}
+
+// Build `{ident}Set`, a bitset of the fieldless enum `ident`, with a bit for
+// each variant at its `index`.
+fn build_set(
+    ident: &Ident,
+    variants: &[&Ident],
+    vis: &Visibility,
+    derive_debug: bool,
+) -> TokenStream2 {
+    let set_ident = set_ident(ident);
+    let iter_ident = format_ident!("{}Iter", set_ident);
+    let bits = bits_type(ident, variants.len());
+    let indices = 0..variants.len() as u32;
+
+    let set_doc = format!(
+        "A set of [`{ident}`]s, stored as a bitset with a bit for each variant.\n\n\
+        It iterates in the order that [`{ident}`]'s variants are declared."
+    );
+    let iter_doc = format!("An iterator over the [`{ident}`]s in a [`{set_ident}`].");
+
+    let debug_impl = derive_debug.then(|| {
+        quote!(
+            impl core::fmt::Debug for #set_ident {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.debug_set().entries(*self).finish()
+                }
+            }
+        )
+    });
+
+    quote!(
+        #[doc = #set_doc]
+        #[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+        #vis struct #set_ident {
+            bits: #bits,
+        }
+
+        #[allow(dead_code)]
+        impl #set_ident {
+            const ALL_BITS: #bits = #bits::MAX >> (#bits::BITS - #ident::COUNT as u32);
+
+            /// Returns an empty set.
+            #vis const fn new() -> Self {
+                Self { bits: 0 }
+            }
+
+            /// Returns a set of every variant.
+            #vis const fn all() -> Self {
+                Self { bits: Self::ALL_BITS }
+            }
+
+            /// Returns whether this set is empty.
+            #vis const fn is_empty(self) -> bool {
+                self.bits == 0
+            }
+
+            /// Returns the number of variants in this set.
+            #vis const fn len(self) -> usize {
+                self.bits.count_ones() as usize
+            }
+
+            /// Returns whether `value` is in this set.
+            #vis const fn contains(self, value: #ident) -> bool {
+                self.bits & (1 << value.index()) != 0
+            }
+
+            /// Adds `value` to this set, returning whether it wasn't already
+            /// there.
+            #vis fn insert(&mut self, value: #ident) -> bool {
+                let bit = 1 << value.index();
+                let inserted = self.bits & bit == 0;
+                self.bits |= bit;
+                inserted
+            }
+
+            /// Removes `value` from this set, returning whether it was there.
+            #vis fn remove(&mut self, value: #ident) -> bool {
+                let bit = 1 << value.index();
+                let removed = self.bits & bit != 0;
+                self.bits &= !bit;
+                removed
+            }
+
+            /// Returns the variants in either set.
+            #vis const fn union(self, other: Self) -> Self {
+                Self { bits: self.bits | other.bits }
+            }
+
+            /// Returns the variants in both sets.
+            #vis const fn intersection(self, other: Self) -> Self {
+                Self { bits: self.bits & other.bits }
+            }
+
+            /// Returns the variants in this set but not in `other`.
+            #vis const fn difference(self, other: Self) -> Self {
+                Self { bits: self.bits & !other.bits }
+            }
+
+            /// Returns an iterator over the variants in this set.
+            #vis const fn iter(self) -> #iter_ident {
+                #iter_ident { bits: self.bits }
+            }
+        }
+
+        #debug_impl
+
+        #[automatically_derived]
+        impl core::iter::FromIterator<#ident> for #set_ident {
+            fn from_iter<I: core::iter::IntoIterator<Item = #ident>>(iter: I) -> Self {
+                let mut set = Self::new();
+                core::iter::Extend::extend(&mut set, iter);
+                set
+            }
+        }
+
+        #[automatically_derived]
+        impl core::iter::Extend<#ident> for #set_ident {
+            fn extend<I: core::iter::IntoIterator<Item = #ident>>(&mut self, iter: I) {
+                for value in iter {
+                    self.insert(value);
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl core::iter::IntoIterator for #set_ident {
+            type Item = #ident;
+            type IntoIter = #iter_ident;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        #[doc = #iter_doc]
+        #[derive(Clone, Debug)]
+        #vis struct #iter_ident {
+            bits: #bits,
+        }
+
+        #[automatically_derived]
+        impl core::iter::Iterator for #iter_ident {
+            type Item = #ident;
+
+            fn next(&mut self) -> core::option::Option<#ident> {
+                if self.bits == 0 {
+                    return core::option::Option::None;
+                }
+                let index = self.bits.trailing_zeros();
+                self.bits &= self.bits - 1;
+                core::option::Option::Some(match index {
+                    #(#indices => #ident::#variants,)*
+                    _ => core::unreachable!(),
+                })
+            }
+
+            fn size_hint(&self) -> (usize, core::option::Option<usize>) {
+                let len = self.bits.count_ones() as usize;
+                (len, core::option::Option::Some(len))
+            }
+        }
+
+        impl core::iter::ExactSizeIterator for #iter_ident {}
+
+        impl core::iter::FusedIterator for #iter_ident {}
+    )
+}
+
+/// Build the parent's `{Parent}Set`, along with conversions to it from each
+/// subenum's set.
+pub fn build_parent_set(parent: &DeriveInput, children: &[Enum]) -> TokenStream2 {
+    let parent_ident = &parent.ident;
+    let parent_set = set_ident(parent_ident);
+    let variants = fieldless_variants(parent_ident, parent_variants(parent), "set");
+    let set = build_set(
+        parent_ident,
+        &variants,
+        &parent.vis,
+        derives_trait(&parent.attrs, "Debug"),
+    );
+    let parent_bits = bits_type(parent_ident, variants.len());
+
+    let conversions = children.iter().filter(|child| child.set).map(|child| {
+        let child_ident = &child.ident;
+        let child_set = set_ident(child_ident);
+        let child_bits = bits_type(child_ident, child.variants.len());
+
+        // Where each of the child's bits goes in the parent's set.
+        let (child_indices, parent_indices): (Vec<u32>, Vec<u32>) = child
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(child_index, variant)| {
+                let parent_index = variants.iter().position(|v| *v == &variant.ident).unwrap();
+                (child_index as u32, parent_index as u32)
+            })
+            .unzip();
+
+        let from_child = quote!(
+            #[automatically_derived]
+            impl core::convert::From<#child_set> for #parent_set {
+                fn from(set: #child_set) -> Self {
+                    let mut bits: #parent_bits = 0;
+                    #(
+                        if set.bits & (1 << #child_indices) != 0 {
+                            bits |= 1 << #parent_indices;
+                        }
+                    )*
+                    Self { bits }
+                }
+            }
+        );
+
+        let from_parent_bits = quote!(
+            let mut bits: #child_bits = 0;
+            #(
+                if set.bits & (1 << #parent_indices) != 0 {
+                    bits |= 1 << #child_indices;
+                }
+            )*
+        );
+
+        // If the child has every variant, converting from the parent's set
+        // can't fail.
+        let from_parent = if child.variants.len() == variants.len() {
+            quote!(
+                #[automatically_derived]
+                impl core::convert::From<#parent_set> for #child_set {
+                    fn from(set: #parent_set) -> Self {
+                        #from_parent_bits
+                        Self { bits }
+                    }
+                }
+            )
+        } else {
+            let error = child.error_ident();
+            let names = variants.iter().map(|v| v.to_string());
+            quote!(
+                #[automatically_derived]
+                impl core::convert::TryFrom<#parent_set> for #child_set {
+                    type Error = #error<#parent_set>;
+
+                    fn try_from(set: #parent_set) -> core::result::Result<Self, <Self as core::convert::TryFrom<#parent_set>>::Error> {
+                        const MASK: #parent_bits = 0 #(| (1 << #parent_indices))*;
+                        const NAMES: &[&str] = &[#(#names),*];
+
+                        let rejected = set.bits & !MASK;
+                        if rejected != 0 {
+                            return Err(#error::new(set, NAMES[rejected.trailing_zeros() as usize]));
+                        }
+                        #from_parent_bits
+                        Ok(Self { bits })
+                    }
+                }
+            )
+        };
+
+        quote!(#from_child #from_parent)
+    });
+
+    quote!(
+        #set
+
+        #(#conversions)*
+    )
+}
+
+impl Enum {
+    /// Build `{Child}Set`, a bitset of this enum.
+    pub fn build_set(&self, parent: &DeriveInput, child_attrs: &[Attribute]) -> TokenStream2 {
+        let variants = fieldless_variants(&self.ident, &self.variants, "set");
+        build_set(
+            &self.ident,
+            &variants,
+            &parent.vis,
+            derives_trait(child_attrs, "Debug") || derives_trait(&self.attributes, "Debug"),
+        )
+    }
+}
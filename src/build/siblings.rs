@@ -0,0 +1,153 @@
+use alloc::{string::ToString, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{punctuated::Punctuated, DeriveInput, Token};
+
+use super::{add_bound, build_conversion, runs::rejected, variant_to_unary_pat};
+use crate::{
+    derive::{partial_eq::partial_eq_arm, Derive},
+    r#enum::Enum,
+};
+
+impl Enum {
+    /// Build a conversion from this enum to its sibling `other`: `From` if all
+    /// of our variants are in `other`, `TryFrom` if only some are, and nothing
+    /// if none are.
+    pub fn build_sibling_conversion(&self, other: &Enum, parent: &DeriveInput) -> TokenStream2 {
+        let shared: Vec<_> = self
+            .variants
+            .iter()
+            .filter(|v| other.contains(&v.ident))
+            .collect();
+        if shared.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let ident = &self.ident;
+        let other_ident = &other.ident;
+        let error = other.error_ident();
+
+        if let (Some(runs), Some(to_parent)) =
+            (self.runs_to(other, parent), self.runs_to_parent(parent))
+        {
+            let read = runs.read(quote!(&value));
+            if shared.len() == self.variants.len() {
+                let value = runs.convert(
+                    read,
+                    quote!(#other_ident),
+                    quote!(unsafe { core::hint::unreachable_unchecked() }),
+                );
+                return quote!(
+                    #[automatically_derived]
+                    impl core::convert::From<#ident> for #other_ident {
+                        fn from(value: #ident) -> Self {
+                            #value
+                        }
+                    }
+                );
+            }
+
+            // Report a rejected variant by its discriminant in the parent.
+            let rejected = rejected(parent, &error);
+            let discriminant = to_parent.map(
+                quote!(discriminant),
+                quote!(unsafe { core::hint::unreachable_unchecked() }),
+            );
+            let value = runs.convert(
+                read,
+                quote!(#other_ident),
+                quote!({
+                    let discriminant = #discriminant;
+                    #rejected
+                }),
+            );
+            return quote!(
+                #[automatically_derived]
+                impl core::convert::TryFrom<#ident> for #other_ident {
+                    type Error = #error<#ident>;
+
+                    fn try_from(value: #ident) -> core::result::Result<Self, <Self as core::convert::TryFrom<#ident>>::Error> {
+                        Ok(#value)
+                    }
+                }
+            );
+        }
+
+        let generics = self.joint_generics(other, &parent.generics);
+        let (_, ty, _) = self.generics.split_for_impl();
+        let (_, other_ty, _) = other.generics.split_for_impl();
+
+        let rejected_arms: Vec<_> = self
+            .variants
+            .iter()
+            .filter(|v| !other.contains(&v.ident))
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let name = variant_ident.to_string();
+                quote!(#ident::#variant_ident { .. } => Err(#error::new(value, #name)))
+            })
+            .collect();
+
+        build_conversion(
+            &generics,
+            quote!(#ident #ty),
+            quote!(#other_ident #other_ty),
+            quote!(#error<#ident #ty>),
+            shared.iter().map(|variant| {
+                let pat = variant_to_unary_pat(variant);
+                (quote!(#ident::#pat), quote!(#other_ident::#pat))
+            }),
+            &rejected_arms,
+        )
+    }
+
+    /// Build the parent's derives that relate this enum to its sibling
+    /// `other`, for those that share variants.
+    pub fn build_sibling_derives(&self, other: &Enum, parent: &DeriveInput) -> TokenStream2 {
+        let shared: Vec<_> = self
+            .variants
+            .iter()
+            .filter(|v| other.contains(&v.ident))
+            .collect();
+        if shared.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let ident = &self.ident;
+        let other_ident = &other.ident;
+        let (_, ty, _) = self.generics.split_for_impl();
+        let (_, other_ty, _) = other.generics.split_for_impl();
+
+        self.derives
+            .iter()
+            .map(|&derive| match derive {
+                Derive::PartialEq => {
+                    if let Some(runs) = self.runs_to(other, parent) {
+                        return self.build_runs_partial_eq(other_ident, &runs);
+                    }
+
+                    let mut generics = self.joint_generics(other, &parent.generics);
+                    add_bound(&mut generics, derive.as_bound());
+                    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+                    let arms: Punctuated<TokenStream2, Token![,]> = shared
+                        .iter()
+                        .map(|variant| partial_eq_arm(variant, ident, other_ident))
+                        .collect();
+
+                    quote!(
+                        #[automatically_derived]
+                        impl #impl_generics PartialEq<#other_ident #other_ty> for #ident #ty #where_clause {
+                            fn eq(&self, other: &#other_ident #other_ty) -> bool {
+                                match (self, other) {
+                                    #arms,
+                                    _ => false,
+                                }
+                            }
+                        }
+                    )
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,49 @@
+use alloc::{string::ToString, vec::Vec};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Path};
+
+use crate::r#enum::Enum;
+
+impl Enum {
+    /// Build our impl of the runtime crate's `SubsetOf<Parent>`, found at
+    /// `runtime`.
+    pub fn build_subset_of(&self, parent: &DeriveInput, runtime: &Path) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let snake = self.ident.to_string().to_snake_case();
+        let is_fn = format_ident!("is_{snake}");
+        let into_fn = format_ident!("into_{snake}");
+
+        let (_, child_ty, _) = self.generics.split_for_impl();
+        let (parent_impl, parent_ty, parent_where) = parent.generics.split_for_impl();
+        let parent = quote!(#parent_ident #parent_ty);
+        let variants: Vec<_> = self.variants.iter().map(|v| v.ident.to_string()).collect();
+
+        quote!(
+            #[automatically_derived]
+            impl #parent_impl #runtime::SubsetOf<#parent> for #child_ident #child_ty #parent_where {
+                type Error = <Self as core::convert::TryFrom<#parent>>::Error;
+
+                const VARIANTS: &'static [&'static str] = &[#(#variants),*];
+
+                fn widen(self) -> #parent {
+                    core::convert::From::from(self)
+                }
+
+                fn narrow(parent: #parent) -> core::result::Result<Self, <Self as #runtime::SubsetOf<#parent>>::Error> {
+                    core::convert::TryFrom::try_from(parent)
+                }
+
+                fn try_narrow(parent: #parent) -> core::result::Result<Self, #parent> {
+                    parent.#into_fn()
+                }
+
+                fn contains(parent: &#parent) -> bool {
+                    parent.#is_fn()
+                }
+            }
+        )
+    }
+}
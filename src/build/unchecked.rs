@@ -0,0 +1,78 @@
+use alloc::format;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::DeriveInput;
+
+use super::{extra_generics, variant_to_unary_pat};
+use crate::r#enum::Enum;
+
+impl Enum {
+    /// Build `{Child}::from_parent_unchecked`, which converts from the parent
+    /// without checking that it's one of our variants.
+    pub fn build_unchecked(&self, parent: &DeriveInput) -> TokenStream2 {
+        let child_ident = &self.ident;
+        let parent_ident = &parent.ident;
+        let vis = &parent.vis;
+
+        let (child_impl, child_ty, child_where) = self.generics.split_for_impl();
+        let (_, parent_ty, _) = parent.generics.split_for_impl();
+        let extra = extra_generics(&self.generics, &parent.generics);
+        let extra_where = &extra.where_clause;
+
+        let variant_idents = self.variants.iter().map(|v| &v.ident);
+        let arms = self
+            .variants
+            .iter()
+            .map(variant_to_unary_pat)
+            .map(|pat| quote!(#parent_ident::#pat => #child_ident::#pat));
+        let rejected_arms = self.rejected_arms(parent, |_| {
+            quote!(unsafe { core::hint::unreachable_unchecked() })
+        });
+
+        let doc = format!(
+            "Converts from a [`{parent_ident}`] without checking that it's one of our variants, \
+            for when that's already known.\n\n\
+            This skips building an error, and lets the compiler drop the check entirely in \
+            release builds. In debug builds, it still panics if `value` isn't one of our \
+            variants.\n\n\
+            # Safety\n\n\
+            `value` must be one of [`{child_ident}`]'s variants; that is, \
+            [`{child_ident}::try_from`](core::convert::TryFrom::try_from) must succeed for it."
+        );
+        let message = format!(
+            "`{child_ident}::from_parent_unchecked` called with a `{parent_ident}` that isn't one \
+            of its variants"
+        );
+
+        let body = match self.runs_from_parent(parent) {
+            Some(runs) => runs.convert(
+                runs.read(quote!(&value)),
+                quote!(Self),
+                quote!({
+                    core::debug_assert!(false, #message);
+                    unsafe { core::hint::unreachable_unchecked() }
+                }),
+            ),
+            None => quote!(
+                core::debug_assert!(
+                    core::matches!(value, #(#parent_ident::#variant_idents { .. })|*),
+                    #message,
+                );
+                match value {
+                    #(#arms,)*
+                    #(#rejected_arms,)*
+                }
+            ),
+        };
+
+        quote!(
+            #[allow(dead_code)]
+            impl #child_impl #child_ident #child_ty #child_where {
+                #[doc = #doc]
+                #vis unsafe fn from_parent_unchecked #extra(value: #parent_ident #parent_ty) -> Self #extra_where {
+                    #body
+                }
+            }
+        )
+    }
+}
@@ -0,0 +1,219 @@
+use alloc::{format, vec::Vec};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, punctuated::Punctuated, Attribute, DeriveInput, Fields, Generics, Ident, Lifetime,
+    Token, Variant,
+};
+
+use super::{add_bound, build_conversion, doc_attrs, variant_to_unary_pat};
+use crate::{derive::derives_trait, r#enum::Enum, snake_case};
+
+pub fn view_lifetime() -> Lifetime {
+    parse_quote!('subenum)
+}
+
+// Prepend our view lifetime to `generics`.
+pub fn with_view_lifetime(generics: &Generics) -> Generics {
+    let lifetime = view_lifetime();
+    let mut generics = generics.clone();
+    generics.params.insert(0, parse_quote!(#lifetime));
+    generics
+}
+
+// Turn a child variant into one borrowing its fields, e.g.
+// * Foo(Bar) -> Foo(&'subenum Bar)
+// * Foo { x: i32 } -> Foo { x: &'subenum mut i32 }
+// Only doc attributes are kept, as the view doesn't derive anything that could
+// make use of the others.
+fn view_variant(variant: &Variant, mutability: Option<Token![mut]>) -> TokenStream2 {
+    let lifetime = view_lifetime();
+    let ident = &variant.ident;
+    let docs = doc_attrs(&variant.attrs);
+
+    let fields = match &variant.fields {
+        Fields::Named(named) => {
+            let fields = named.named.iter().map(|field| {
+                let docs = doc_attrs(&field.attrs);
+                let ident = &field.ident;
+                let ty = &field.ty;
+                quote!(#(#docs)* #ident: &#lifetime #mutability #ty)
+            });
+            quote!({ #(#fields),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let fields = unnamed.unnamed.iter().map(|field| {
+                let ty = &field.ty;
+                quote!(&#lifetime #mutability #ty)
+            });
+            quote!(( #(#fields),* ))
+        }
+        Fields::Unit => quote!(),
+    };
+
+    quote!(#(#docs)* #ident #fields)
+}
+
+// Build a child variant out of the bindings from `variant_to_unary_pat`,
+// cloning each of them.
+fn variant_to_cloned_expr(variant: &Variant) -> TokenStream2 {
+    let ident = &variant.ident;
+
+    match &variant.fields {
+        Fields::Named(named) => {
+            let fields: Punctuated<TokenStream2, Token![,]> = named
+                .named
+                .iter()
+                .map(|field| {
+                    let var = snake_case(field);
+                    let ident = &field.ident;
+                    quote!(#ident: core::clone::Clone::clone(#var))
+                })
+                .collect();
+            quote!(#ident { #fields })
+        }
+        Fields::Unnamed(unnamed) => {
+            let fields: Punctuated<TokenStream2, Token![,]> = unnamed
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(idx, _)| {
+                    let var = format_ident!("var{idx}");
+                    quote!(core::clone::Clone::clone(#var))
+                })
+                .collect();
+            quote!(#ident(#fields))
+        }
+        Fields::Unit => quote!(#ident),
+    }
+}
+
+impl Enum {
+    pub fn ref_ident(&self) -> Ident {
+        format_ident!("{}Ref", self.ident)
+    }
+
+    pub fn mut_ident(&self) -> Ident {
+        format_ident!("{}Mut", self.ident)
+    }
+
+    /// Build the `{Child}Ref` and `{Child}Mut` views, along with conversions to
+    /// them from borrowed parents.
+    pub fn build_views(&self, parent: &DeriveInput, child_attrs: &[Attribute]) -> TokenStream2 {
+        // Without any fields, the views would have nothing to borrow and their
+        // lifetime would go unused.
+        if self.variants.iter().all(|v| v.fields.is_empty()) {
+            panic!(
+                "`{}` has no fields to borrow, so it can't have `refs`",
+                self.ident
+            );
+        }
+
+        let lifetime = view_lifetime();
+        let child_ident = &self.ident;
+        let ref_ident = self.ref_ident();
+        let mut_ident = self.mut_ident();
+        let parent_ident = &parent.ident;
+        let error = self.error_ident();
+        let vis = &parent.vis;
+
+        let view_generics = with_view_lifetime(&self.generics);
+        let (view_impl, view_ty, view_where) = view_generics.split_for_impl();
+
+        let impl_generics = with_view_lifetime(&parent.generics);
+        let (_, parent_ty, _) = parent.generics.split_for_impl();
+        let (_, child_ty, _) = self.generics.split_for_impl();
+
+        let ref_variants = self.variants.iter().map(|v| view_variant(v, None));
+        let mut_variants = self
+            .variants
+            .iter()
+            .map(|v| view_variant(v, Some(Default::default())));
+
+        let derive_debug = (derives_trait(child_attrs, "Debug")
+            || derives_trait(&self.attributes, "Debug"))
+        .then(|| quote!(#[derive(Debug)]));
+
+        let pats: Vec<TokenStream2> = self.variants.iter().map(variant_to_unary_pat).collect();
+        let rejected_arms =
+            self.rejected_arms(parent, |variant| quote!(Err(#error::new(value, #variant))));
+
+        let from_ref = build_conversion(
+            &impl_generics,
+            quote!(&#lifetime #parent_ident #parent_ty),
+            quote!(#ref_ident #view_ty),
+            quote!(#error<&#lifetime #parent_ident #parent_ty>),
+            pats.iter()
+                .map(|pat| (quote!(#parent_ident::#pat), quote!(#ref_ident::#pat))),
+            &rejected_arms,
+        );
+        let from_mut = build_conversion(
+            &impl_generics,
+            quote!(&#lifetime mut #parent_ident #parent_ty),
+            quote!(#mut_ident #view_ty),
+            quote!(#error<&#lifetime mut #parent_ident #parent_ty>),
+            pats.iter()
+                .map(|pat| (quote!(#parent_ident::#pat), quote!(#mut_ident::#pat))),
+            &rejected_arms,
+        );
+
+        let ref_doc = format!(
+            "A borrowed view of a [`{parent_ident}`] that is an [`{child_ident}`], holding \
+            references to its fields."
+        );
+        let mut_doc = format!(
+            "A mutably borrowed view of a [`{parent_ident}`] that is an [`{child_ident}`], \
+            holding mutable references to its fields."
+        );
+
+        // If the child can be cloned, it can also be made from a borrowed parent.
+        let is_clone =
+            derives_trait(child_attrs, "Clone") || derives_trait(&self.attributes, "Clone");
+        let cloned_impl = is_clone.then(|| {
+            let mut clone_generics = impl_generics.clone();
+            add_bound(&mut clone_generics, parse_quote!(core::clone::Clone));
+
+            build_conversion(
+                &clone_generics,
+                quote!(&#lifetime #parent_ident #parent_ty),
+                quote!(#child_ident #child_ty),
+                quote!(#error<&#lifetime #parent_ident #parent_ty>),
+                self.variants.iter().zip(&pats).map(|(variant, pat)| {
+                    let expr = variant_to_cloned_expr(variant);
+                    (quote!(#parent_ident::#pat), quote!(#child_ident::#expr))
+                }),
+                &rejected_arms,
+            )
+        });
+
+        quote!(
+            #[doc = #ref_doc]
+            #derive_debug
+            #[allow(dead_code)]
+            #vis enum #ref_ident #view_generics #view_where {
+                #(#ref_variants),*
+            }
+
+            impl #view_impl core::clone::Clone for #ref_ident #view_ty #view_where {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl #view_impl core::marker::Copy for #ref_ident #view_ty #view_where {}
+
+            #[doc = #mut_doc]
+            #derive_debug
+            #[allow(dead_code)]
+            #vis enum #mut_ident #view_generics #view_where {
+                #(#mut_variants),*
+            }
+
+            #from_ref
+
+            #from_mut
+
+            #cloned_impl
+        )
+    }
+}
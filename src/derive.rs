@@ -1,5 +1,9 @@
+use alloc::vec::Vec;
 use proc_macro2::Span;
-use syn::{Ident, Path, TraitBound, TraitBoundModifier, TypeParamBound};
+use syn::{
+    punctuated::Punctuated, Attribute, Ident, Path, Token, TraitBound, TraitBoundModifier,
+    TypeParamBound,
+};
 
 pub mod partial_eq;
 
@@ -20,3 +24,49 @@ impl Derive {
         }
     }
 }
+
+/// Whether `attrs` contain a `#[derive(..)]` of a trait named `name`, such as
+/// `Clone` or `core::clone::Clone`.
+pub fn derives_trait(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| path.segments.last().is_some_and(|seg| seg.ident == name))
+}
+
+// Traits that can be derived for any enum whose fields implement them, without
+// any helper attributes.
+const STD_DERIVES: &[&str] = &[
+    "Clone",
+    "Copy",
+    "Debug",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+];
+
+/// The standard library traits derived in `attrs`, for generated types that
+/// can't take on arbitrary attributes.
+pub fn std_derives(attrs: &[Attribute]) -> Vec<Path> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter(|path| {
+            path.segments
+                .last()
+                .is_some_and(|seg| STD_DERIVES.iter().any(|name| seg.ident == name))
+        })
+        .collect()
+}
@@ -3,8 +3,30 @@ use crate::visitor::ParamVisitor;
 use crate::{param::Param, Derive};
 use alloc::{collections::BTreeSet, vec::Vec};
 use syn::visit::Visit;
-use syn::Attribute;
 use syn::{punctuated::Punctuated, Generics, Ident, Token, Variant, WherePredicate};
+use syn::{Attribute, Expr, Meta};
+
+// Subenum-level options, e.g. `#[subenum(Edible(refs))]`.
+const REFS: &str = "refs";
+const SET: &str = "set";
+const MAP: &str = "map";
+const DISCRIMINANTS: &str = "discriminants";
+const LAYOUT_COMPATIBLE: &str = "layout_compatible";
+const PAT: &str = "pat";
+
+// Variant-level options, e.g. `#[subenum(Pack(discriminant = 1))]`.
+const DISCRIMINANT: &str = "discriminant";
+
+/// How a subenum's variants get their discriminants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Discriminants {
+    /// Keep the parent's explicit discriminants.
+    Inherit,
+    /// Number the variants from 0, in order.
+    Dense,
+    /// Use the discriminants given with `discriminant = N` on each variant.
+    Explicit,
+}
 
 pub struct Enum {
     pub ident: Ident,
@@ -13,6 +35,34 @@ pub struct Enum {
     pub attributes: Vec<Attribute>,
     pub derives: Vec<Derive>,
     pub generics: Generics,
+    /// Whether to generate `{Child}Ref` and `{Child}Mut` borrowed views.
+    pub refs: bool,
+    /// Whether to generate a `{Child}Set` bitset.
+    pub set: bool,
+    /// Whether to generate a `{Child}Map<V>` keyed by this enum.
+    pub map: bool,
+    /// Whether to export our `{child}_pat!` macro from the crate, so other
+    /// crates can use it if we're public.
+    pub pat: bool,
+    /// How our variants get their discriminants, from
+    /// `discriminants = inherit|dense|explicit`.
+    pub discriminants: Discriminants,
+    /// Whether to cast references to and from the parent, as our layouts are
+    /// compatible.
+    pub layout_compatible: bool,
+    /// The enum this one was declared within, e.g. `Num` for `Int` in
+    /// `#[subenum(Num(Int))]`. All of our variants are its variants, too.
+    pub superset: Option<Ident>,
+    /// The enum with exactly the parent's variants that this one doesn't have,
+    /// e.g. `Inedible` for `Edible` in `#[subenum(Edible, Inedible = !Edible)]`,
+    /// and vice versa.
+    pub complement: Option<Ident>,
+    /// Whether this enum was declared as a complement, and so gets its
+    /// variants from the enum it complements.
+    pub is_complement: bool,
+    /// The hints of the `#[repr(..)]` we're declared with, whether shared
+    /// with the parent or given for us alone.
+    pub repr: Vec<Ident>,
 }
 
 impl Enum {
@@ -29,9 +79,113 @@ impl Enum {
                 gt_token: Some(syn::token::Gt::default()),
                 where_clause: None,
             },
+            refs: false,
+            set: false,
+            map: false,
+            pat: false,
+            discriminants: Discriminants::Inherit,
+            layout_compatible: false,
+            superset: None,
+            complement: None,
+            is_complement: false,
+            repr: Vec::new(),
+        }
+    }
+
+    /// Add `variant` to this enum. A variant may be added more than once, when
+    /// it's listed for both this enum and one nested within it; then its
+    /// attributes are merged.
+    ///
+    /// A `discriminant = N` among `attributes` sets its discriminant, with
+    /// `discriminants = explicit`.
+    pub fn push_variant(&mut self, mut variant: Variant, mut attributes: Vec<Attribute>) {
+        let mut discriminant = None;
+        attributes.retain(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident(DISCRIMINANT) => {
+                discriminant = Some((Default::default(), nv.value.clone()));
+                false
+            }
+            _ => true,
+        });
+
+        match self.discriminants {
+            Discriminants::Inherit | Discriminants::Dense if discriminant.is_some() => panic!(
+                "`{}` needs `discriminants = explicit` to set the discriminant of `{}`",
+                self.ident, variant.ident
+            ),
+            Discriminants::Inherit => {}
+            Discriminants::Dense => variant.discriminant = None,
+            Discriminants::Explicit => variant.discriminant = discriminant.clone(),
+        }
+
+        match self.variants.last_mut() {
+            Some(last) if last.ident == variant.ident => {
+                if discriminant.is_some() {
+                    last.discriminant = discriminant;
+                }
+                self.variants_attributes
+                    .last_mut()
+                    .unwrap()
+                    .extend(attributes);
+            }
+            _ => {
+                self.variants.push(variant);
+                self.variants_attributes.push(attributes);
+            }
         }
     }
 
+    /// Apply `meta` if it's one of our options rather than an attribute to
+    /// pass through, returning whether it was.
+    pub fn set_option(&mut self, meta: &Meta) -> bool {
+        match meta {
+            Meta::Path(path) if path.is_ident(REFS) => {
+                self.refs = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(SET) => {
+                self.set = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(MAP) => {
+                self.map = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(PAT) => {
+                self.pat = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(LAYOUT_COMPATIBLE) => {
+                self.layout_compatible = true;
+                true
+            }
+            Meta::NameValue(nv) if nv.path.is_ident(DISCRIMINANTS) => {
+                self.discriminants = match &nv.value {
+                    Expr::Path(path) if path.path.is_ident("inherit") => Discriminants::Inherit,
+                    Expr::Path(path) if path.path.is_ident("dense") => Discriminants::Dense,
+                    Expr::Path(path) if path.path.is_ident("explicit") => Discriminants::Explicit,
+                    _ => panic!("`discriminants` must be one of `inherit`, `dense` or `explicit`"),
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `variant` is one of this enum's variants.
+    pub fn contains(&self, variant: &Ident) -> bool {
+        self.variants.iter().any(|v| &v.ident == variant)
+    }
+
+    /// The generics needed to mention both this enum and `other` in one impl.
+    pub fn joint_generics(&self, other: &Enum, parent_generics: &Generics) -> Generics {
+        let mut joint = Enum::new(self.ident.clone(), Vec::new(), Vec::new());
+        joint.variants.extend(self.variants.iter().cloned());
+        joint.variants.extend(other.variants.iter().cloned());
+        joint.compute_generics(parent_generics);
+        joint.generics
+    }
+
     pub fn compute_generics(&mut self, parent_generics: &Generics) {
         // 1. Analyze constraints: Convert all inline bounds and where clauses
         //    into a list of PredicateDependency
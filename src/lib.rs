@@ -6,6 +6,7 @@ extern crate alloc;
 mod build;
 mod derive;
 mod r#enum;
+mod options;
 mod param;
 mod predicate;
 mod visitor;
@@ -14,12 +15,14 @@ use alloc::{borrow::ToOwned, collections::BTreeMap, string::ToString, vec::Vec};
 
 use derive::Derive;
 use heck::ToSnakeCase;
+use options::Options;
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::quote;
 use r#enum::Enum;
+use syn::parse::Parser;
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, DeriveInput, Field, Meta, Token, Type};
+use syn::{DeriveInput, Expr, ExprUnary, Field, Meta, Token, Type, UnOp};
 
 const SUBENUM: &str = "subenum";
 const ERR: &str =
@@ -56,37 +59,139 @@ fn sanitize_input(input: &mut DeriveInput) {
     }
 }
 
+// Whether `meta` declares a nested subenum, like `Int` in `Num(Int, Float)`,
+// rather than an attribute. We go by case, as enums are `UpperCamelCase` and
+// attributes are `snake_case`.
+fn is_nested_enum(meta: &Meta) -> bool {
+    match meta {
+        Meta::Path(path) => path.get_ident(),
+        Meta::List(ml) => ml.path.get_ident(),
+        Meta::NameValue(nv) => nv.path.get_ident(),
+    }
+    .and_then(|ident| ident.to_string().chars().next())
+    .is_some_and(char::is_uppercase)
+}
+
+// Add the enum declared by `meta` to `enums`, along with any nested within it.
+fn add_enum(
+    meta: Meta,
+    superset: Option<&Ident>,
+    derives: &[Derive],
+    enums: &mut BTreeMap<Ident, Enum>,
+) {
+    let (ident, metas, complement) = match meta {
+        Meta::Path(path) => (
+            path.get_ident().expect(ERR).to_owned(),
+            Punctuated::new(),
+            None,
+        ),
+        Meta::List(ml) => (
+            ml.path.get_ident().expect(ERR).to_owned(),
+            ml.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .expect(ERR),
+            None,
+        ),
+        Meta::NameValue(nv) => (
+            nv.path.get_ident().expect(ERR).to_owned(),
+            Punctuated::new(),
+            Some(complemented_enum(&nv.value)),
+        ),
+    };
+
+    let mut e = Enum::new(ident.clone(), Vec::new(), derives.to_owned());
+    e.superset = superset.cloned();
+    if complement.is_some() {
+        // A complement isn't a subset of anything but the parent.
+        if superset.is_some() {
+            panic!("`{ident}` is a complement, so it must be declared at the top level");
+        }
+        e.complement = complement;
+        e.is_complement = true;
+    }
+
+    let mut nested = Vec::new();
+    for meta in metas {
+        if is_nested_enum(&meta) {
+            nested.push(meta);
+        } else if !e.set_option(&meta) {
+            e.attributes.push(syn::parse_quote!(#[#meta]));
+        }
+    }
+
+    if enums.insert(ident.clone(), e).is_some() {
+        panic!("`{ident}` is declared more than once");
+    }
+    for meta in nested {
+        add_enum(meta, Some(&ident), derives, enums);
+    }
+}
+
+// The enum complemented by `expr`, like `Edible` in `Inedible = !Edible`.
+fn complemented_enum(expr: &Expr) -> Ident {
+    match expr {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Not(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Path(path) => path.path.get_ident().cloned(),
+            _ => None,
+        },
+        _ => None,
+    }
+    .expect("a complement must be declared like `Inedible = !Edible`")
+}
+
+// Pairs of each declared complement with the enum it complements.
+fn complements(enums: &BTreeMap<Ident, Enum>) -> Vec<(Ident, Ident)> {
+    enums
+        .values()
+        .filter(|e| e.is_complement)
+        .map(|e| (e.ident.clone(), e.complement.clone().unwrap()))
+        .collect()
+}
+
 fn build_enum_map(
     args: Punctuated<Meta, syn::Token![,]>,
     derives: &[Derive],
-) -> BTreeMap<Ident, Enum> {
-    args.into_iter()
-        .map(|meta| match meta {
-            Meta::Path(path) => (path.get_ident().expect(ERR).to_owned(), Vec::new()),
-            Meta::List(ml) => (
-                ml.path.get_ident().expect(ERR).to_owned(),
-                ml.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                    .expect(ERR)
-                    .into_iter()
-                    .map(|meta| syn::parse_quote!(#[#meta]))
-                    .collect(),
-            ),
-
-            _ => panic!("{}", ERR),
-        })
-        .map(|(ident, attrs)| {
-            (
-                ident.clone(),
-                Enum::new(ident.clone(), attrs, derives.to_owned()),
-            )
-        })
-        .collect()
+) -> (BTreeMap<Ident, Enum>, Options) {
+    let mut enums = BTreeMap::new();
+    let mut options = Options::default();
+    for meta in args {
+        if is_nested_enum(&meta) || !options.set_option(&meta) {
+            add_enum(meta, None, derives, &mut enums);
+        }
+    }
+
+    // Let each complemented enum know its complement.
+    let complements = complements(&enums);
+    for (complement, ident) in complements {
+        let e = enums.get_mut(&ident).unwrap_or_else(|| {
+            panic!("`{complement}` is the complement of `{ident}`, which isn't declared")
+        });
+        if e.complement.replace(complement).is_some() {
+            panic!("`{ident}` has more than one complement");
+        }
+    }
+    (enums, options)
 }
 
 #[proc_macro_attribute]
 pub fn subenum(args: TokenStream, tokens: TokenStream) -> TokenStream {
-    let args = parse_macro_input!(args with Punctuated::<Meta, syn::Token![,]>::parse_terminated);
-    let mut input = parse_macro_input!(tokens as DeriveInput);
+    expand(args.into(), tokens.into()).into()
+}
+
+// The expansion of `#[subenum(args)]` on `tokens`, kept apart from the proc
+// macro itself so it can run outside of the compiler.
+fn expand(args: TokenStream2, tokens: TokenStream2) -> TokenStream2 {
+    let args = match Punctuated::<Meta, syn::Token![,]>::parse_terminated.parse2(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let mut input = match syn::parse2::<DeriveInput>(tokens) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error(),
+    };
     let data = match input.data {
         syn::Data::Enum(ref mut data) => data,
         _ => panic!("subenum may only be used on enums."),
@@ -110,7 +215,8 @@ pub fn subenum(args: TokenStream, tokens: TokenStream) -> TokenStream {
             }
         }
     }
-    let mut enums = build_enum_map(args, &derives);
+    let (mut enums, options) = build_enum_map(args, &derives);
+    let complements = complements(&enums);
 
     let mut self_variant_attrs = alloc::vec![Vec::new(); data.variants.len()];
     for (variant, self_attrs) in data.variants.iter().zip(&mut self_variant_attrs) {
@@ -147,11 +253,43 @@ pub fn subenum(args: TokenStream, tokens: TokenStream) -> TokenStream {
                     let e = enums
                         .get_mut(&ident)
                         .expect("All enums to be created must be declared at the top-level subenum attribute");
-                    e.variants.push(var);
-                    e.variants_attributes.push(attrs);
+                    e.push_variant(var.clone(), attrs);
+
+                    // A variant of a nested enum belongs to every enum it's
+                    // nested within, too.
+                    let mut superset = e.superset.clone();
+                    while let Some(ident) = superset {
+                        let e = enums.get_mut(&ident).unwrap();
+                        e.push_variant(var.clone(), Vec::new());
+                        superset = e.superset.clone();
+                    }
                 }
             }
         }
+
+        // A complement has each variant that the enum it complements doesn't.
+        for (complement, ident) in &complements {
+            if !enums[ident].contains(&variant.ident) {
+                let mut var = variant.clone();
+                var.attrs.retain(|attr| !attr.path().is_ident(SUBENUM));
+                enums
+                    .get_mut(complement)
+                    .unwrap()
+                    .push_variant(var, Vec::new());
+            } else if enums[complement].contains(&variant.ident) {
+                panic!(
+                    "`{}` can't be in both `{ident}` and its complement, `{complement}`",
+                    variant.ident
+                );
+            }
+        }
+    }
+    for (complement, ident) in &complements {
+        if enums[complement].variants.is_empty() {
+            panic!(
+                "`{ident}` has every variant, so its complement, `{complement}`, would be empty"
+            );
+        }
     }
     for (variants, self_attrs) in data.variants.iter_mut().zip(self_variant_attrs) {
         variants.attrs.extend(self_attrs);
@@ -162,17 +300,199 @@ pub fn subenum(args: TokenStream, tokens: TokenStream) -> TokenStream {
     }
 
     let attrs = input.attrs.clone();
-    let enums: Vec<_> = enums
-        .into_values()
-        .map(|e| e.build(&mut input, &attrs))
+    for e in enums.values_mut() {
+        e.repr = build::repr_hints(attrs.iter().chain(&e.attributes));
+    }
+
+    // The parent itself may be listed to give it attributes and options of
+    // its own.
+    let (parent_set, parent_map) = match enums.remove(&input.ident) {
+        Some(e) => {
+            input.attrs.extend(e.attributes);
+            (e.set, e.map)
+        }
+        None => (false, false),
+    };
+
+    let children: Vec<Enum> = enums.into_values().collect();
+    let enums: Vec<_> = children
+        .iter()
+        .map(|e| {
+            let complement = e
+                .complement
+                .as_ref()
+                .and_then(|ident| children.iter().find(|c| &c.ident == ident));
+            e.build(&input, &attrs, complement)
+        })
+        .collect();
+    let siblings: Vec<_> = children
+        .iter()
+        .flat_map(|a| {
+            children
+                .iter()
+                .filter(move |b| a.ident != b.ident)
+                .map(|b| {
+                    let conversion = a.build_sibling_conversion(b, &input);
+                    let derives = a.build_sibling_derives(b, &input);
+                    quote!(#conversion #derives)
+                })
+        })
         .collect();
+    let classify = options
+        .classify
+        .then(|| build::build_classify(&input, &children, &attrs));
+    let parent_index = build::build_parent_index(&input);
+    let parent_variant_name = build::build_parent_variant_name(&input);
+    let parent_set = parent_set.then(|| build::build_parent_set(&input, &children));
+    let parent_map = parent_map.then(|| build::build_parent_map(&input, &children));
+    let iter_ext = options
+        .iter
+        .then(|| build::build_iter_ext(&input, &children));
+    let introspection = options
+        .introspect
+        .then(|| build::build_introspection(&input, &children));
+    let subset_of: Vec<_> = match &options.runtime {
+        Some(runtime) => children
+            .iter()
+            .map(|e| e.build_subset_of(&input, runtime))
+            .collect(),
+        None => Vec::new(),
+    };
 
     sanitize_input(&mut input);
 
     quote!(
         #input
 
+        #parent_index
+
+        #parent_variant_name
+
+        #parent_set
+
+        #parent_map
+
         #(#enums)*
+
+        #(#siblings)*
+
+        #classify
+
+        #iter_ext
+
+        #introspection
+
+        #(#subset_of)*
     )
-    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String};
+    use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+
+    use super::expand;
+
+    const SUBENUMS: usize = 12;
+
+    // The number of tokens in `tokens`, counting those within groups.
+    fn token_count(tokens: TokenStream2) -> usize {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Group(group) => 1 + token_count(group.stream()),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    // The size of the expansion of a fieldless `repr(u16)` enum with
+    // `variants` variants, each in a few of `SUBENUMS` subenums. With
+    // `literal`, its discriminants are all literals, so it can use runs.
+    // Otherwise, the first is named by a const, which needs a match arm per
+    // variant instead.
+    fn expansion_size(variants: usize, literal: bool) -> usize {
+        let subenums: String = (0..SUBENUMS).map(|k| format!("S{k},")).collect();
+        let variants: String = (0..variants)
+            .map(|i| {
+                let subenums: String = (0..SUBENUMS)
+                    .filter(|k| (i / 8 + k) % 3 == 0)
+                    .map(|k| format!("S{k},"))
+                    .collect();
+                let discriminant = match (i, literal) {
+                    (0, true) => " = 0",
+                    (0, false) => " = FIRST",
+                    _ => "",
+                };
+                format!("#[subenum({subenums})] V{i}{discriminant},")
+            })
+            .collect();
+        let tokens = format!(
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq)] #[repr(u16)] enum Opcode {{ {variants} }}"
+        );
+        token_count(expand(subenums.parse().unwrap(), tokens.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_expansion_size_is_linear() {
+        let small = expansion_size(150, true);
+        let large = expansion_size(600, true);
+        // Four times the variants may cost at most a little over four times
+        // the tokens.
+        assert!(
+            large * 10 <= small * 45,
+            "expanding 600 variants took {large} tokens, but 150 took {small}"
+        );
+
+        // An arm per variant grows linearly, too, but is far larger.
+        let arms = expansion_size(600, false);
+        assert!(
+            large * 4 <= arms,
+            "expanding 600 variants took {large} tokens with runs, but {arms} without"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't also have one for the subenum `Other`")]
+    fn test_classify_other_clash() {
+        expand(
+            "Other, Edible, classify".parse().unwrap(),
+            "enum Plant { #[subenum(Other)] Pine, #[subenum(Edible)] Basil, Moss }"
+                .parse()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "generates `PlantOther`")]
+    fn test_classify_parent_other_clash() {
+        expand(
+            "PlantOther, classify".parse().unwrap(),
+            "enum Plant { #[subenum(PlantOther)] Pine, Moss }"
+                .parse()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "has an integer `repr`, like `#[repr(C, u8)]`")]
+    fn test_layout_compatible_c_with_fields() {
+        expand(
+            "Val(layout_compatible)".parse().unwrap(),
+            "#[repr(C)] enum Storage { #[subenum(Val)] A(u32, u8), B(u8, u32) }"
+                .parse()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the same `repr` as `Storage`")]
+    fn test_layout_compatible_parent_only_repr() {
+        expand(
+            "Storage(repr(u8)), Val(layout_compatible)".parse().unwrap(),
+            "enum Storage { #[subenum(Val)] A(u32), B }"
+                .parse()
+                .unwrap(),
+        );
+    }
 }
@@ -0,0 +1,56 @@
+use syn::{Expr, Meta, Path};
+
+// Parent-level options, e.g. `#[subenum(Tree, Grass, classify)]`.
+const CLASSIFY: &str = "classify";
+const ITER: &str = "iter";
+const INTROSPECT: &str = "introspect";
+const RUNTIME: &str = "runtime";
+const CRATE: &str = "crate";
+
+#[derive(Default)]
+pub struct Options {
+    /// Whether to generate `{Parent}Class` and `{Parent}::classify`.
+    pub classify: bool,
+    /// Whether to generate the `{Parent}IterExt` iterator extension trait.
+    pub iter: bool,
+    /// Whether to generate `{Parent}Subenum` and the parent's membership
+    /// table.
+    pub introspect: bool,
+    /// The path to the runtime crate, to implement its `SubsetOf` for each
+    /// subenum, from `runtime`, or `crate = path` for those who re-export it.
+    pub runtime: Option<Path>,
+}
+
+impl Options {
+    /// Apply `meta` if it's one of our options rather than a subenum
+    /// declaration, returning whether it was.
+    pub fn set_option(&mut self, meta: &Meta) -> bool {
+        match meta {
+            Meta::Path(path) if path.is_ident(CLASSIFY) => {
+                self.classify = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(ITER) => {
+                self.iter = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(INTROSPECT) => {
+                self.introspect = true;
+                true
+            }
+            Meta::Path(path) if path.is_ident(RUNTIME) => {
+                self.runtime
+                    .get_or_insert_with(|| syn::parse_quote!(::subenum_runtime));
+                true
+            }
+            Meta::NameValue(nv) if nv.path.is_ident(CRATE) => {
+                let Expr::Path(path) = &nv.value else {
+                    panic!("`crate` must be a path, like `crate = my_crate::subenum_runtime`");
+                };
+                self.runtime = Some(path.path.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use subenum::subenum;
+
+#[subenum(Edible(refs), FlowerBed)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant<T> {
+    #[subenum(Edible)]
+    Basil(T),
+    #[subenum(Edible, FlowerBed)]
+    Tomato,
+    #[subenum(FlowerBed)]
+    Rose { color: String },
+}
+
+#[test]
+fn test_is() {
+    assert!(Plant::Basil(1).is_edible());
+    assert!(Plant::<u8>::Tomato.is_edible());
+    assert!(Plant::<u8>::Tomato.is_flower_bed());
+    assert!(!Plant::<u8>::Rose {
+        color: "red".into()
+    }
+    .is_edible());
+}
+
+#[test]
+fn test_into() {
+    assert_eq!(Plant::Basil(1).into_edible(), Ok(Edible::Basil(1)));
+
+    let rose = Plant::<u8>::Rose {
+        color: "red".into(),
+    };
+    assert_eq!(rose.clone().into_edible(), Err(rose.clone()));
+    assert_eq!(
+        rose.into_flower_bed(),
+        Ok(FlowerBed::Rose {
+            color: "red".into()
+        })
+    );
+}
+
+#[test]
+fn test_as() {
+    let mut plant = Plant::Basil(1);
+
+    assert!(matches!(plant.as_edible(), Some(EdibleRef::Basil(1))));
+    if let Some(EdibleMut::Basil(x)) = plant.as_edible_mut() {
+        *x = 2;
+    }
+    assert_eq!(plant, Plant::Basil(2));
+
+    assert!(Plant::<u8>::Rose {
+        color: "red".into()
+    }
+    .as_edible()
+    .is_none());
+}
@@ -0,0 +1,129 @@
+use subenum::subenum;
+
+#[subenum(Val(layout_compatible), Num)]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u8)]
+enum Storage {
+    I8(i8),
+    #[subenum(Val)]
+    V128([u8; 16]),
+    #[subenum(Val, Num)]
+    F64(f64),
+    #[subenum(Val, Num)]
+    I32(i32),
+    #[subenum(Val)]
+    Ref(Box<str>),
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_vec_reuses_allocation() {
+    let mut storage = Vec::with_capacity(8);
+    storage.push(Storage::I32(1));
+    storage.push(Storage::Ref("big".into()));
+    let ptr = storage.as_ptr();
+
+    let vals = Val::try_from_vec(storage).unwrap();
+    assert_eq!(vals, vec![Val::I32(1), Val::Ref("big".into())]);
+    assert_eq!(vals.as_ptr().cast::<Storage>(), ptr);
+    assert_eq!(vals.capacity(), 8);
+
+    let storage = Val::into_parent_vec(vals);
+    assert_eq!(storage, vec![Storage::I32(1), Storage::Ref("big".into())]);
+    assert_eq!(storage.as_ptr(), ptr);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_vec_rejected() {
+    let storage = vec![Storage::F64(1.5), Storage::I8(2)];
+    let ptr = storage.as_ptr();
+    let storage = Val::try_from_vec(storage).unwrap_err();
+    assert_eq!(storage, vec![Storage::F64(1.5), Storage::I8(2)]);
+    assert_eq!(storage.as_ptr(), ptr);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_boxed_slice() {
+    let storage: Box<[Storage]> = Box::new([Storage::F64(1.5), Storage::V128([1; 16])]);
+    let ptr = storage.as_ptr();
+    let vals = Val::try_from_boxed_slice(storage).unwrap();
+    assert_eq!(vals.as_ptr().cast::<Storage>(), ptr);
+    assert_eq!(
+        &*Val::into_parent_boxed_slice(vals),
+        &[Storage::F64(1.5), Storage::V128([1; 16])]
+    );
+
+    let storage: Box<[Storage]> = Box::new([Storage::I8(1)]);
+    assert_eq!(
+        &*Val::try_from_boxed_slice(storage).unwrap_err(),
+        &[Storage::I8(1)]
+    );
+}
+
+#[test]
+fn test_slice() {
+    let storage = [Storage::I32(3), Storage::F64(0.5)];
+    let vals = Val::try_from_slice(&storage).unwrap();
+    assert_eq!(vals, &[Val::I32(3), Val::F64(0.5)]);
+    assert!(std::ptr::eq(Val::as_parent_slice(vals), &storage[..]));
+
+    let storage = [Storage::I32(3), Storage::I8(0)];
+    assert!(std::ptr::eq(
+        Val::try_from_slice(&storage).unwrap_err(),
+        &storage[..]
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_element_wise() {
+    let storage = vec![Storage::F64(1.5), Storage::I32(2)];
+    let nums = Num::try_from_vec(storage).unwrap();
+    assert_eq!(nums, vec![Num::F64(1.5), Num::I32(2)]);
+    assert_eq!(
+        Num::into_parent_vec(nums),
+        vec![Storage::F64(1.5), Storage::I32(2)]
+    );
+
+    let storage = vec![Storage::F64(1.5), Storage::Ref("no".into())];
+    assert_eq!(
+        Num::try_from_vec(storage).unwrap_err(),
+        vec![Storage::F64(1.5), Storage::Ref("no".into())]
+    );
+
+    let storage: Box<[Storage]> = Box::new([Storage::I32(7)]);
+    assert_eq!(
+        &*Num::try_from_boxed_slice(storage).unwrap(),
+        &[Num::I32(7)]
+    );
+}
+
+#[subenum(Wide(layout_compatible), Other)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C, u16)]
+enum Generic<T, U> {
+    #[subenum(Wide)]
+    A(T),
+    #[subenum(Other)]
+    B(U),
+    #[subenum(Wide, Other)]
+    C,
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_generic_bulk() {
+    let parents: Vec<Generic<u64, u8>> = vec![Generic::A(1), Generic::C];
+    let wides = Wide::try_from_vec(parents).unwrap();
+    assert_eq!(wides, vec![Wide::A(1), Wide::C]);
+    assert_eq!(
+        Wide::into_parent_vec::<u8>(wides),
+        vec![Generic::A(1), Generic::C]
+    );
+
+    let parents: Vec<Generic<u64, u8>> = vec![Generic::B(1), Generic::C];
+    let others = Other::try_from_vec(parents).unwrap();
+    assert_eq!(others, vec![Other::B(1), Other::C]);
+}
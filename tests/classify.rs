@@ -0,0 +1,78 @@
+use subenum::subenum;
+
+#[subenum(Tree, Grass, classify)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Tree)]
+    Pine,
+    #[subenum(Tree)]
+    Oak {
+        age: u32,
+    },
+    #[subenum(Grass)]
+    Bamboo(u8),
+    Moss,
+}
+
+fn describe(plant: Plant) -> String {
+    match plant.classify() {
+        PlantClass::Tree(tree) => format!("tree: {tree:?}"),
+        PlantClass::Grass(Grass::Bamboo(height)) => format!("bamboo of {height}"),
+        PlantClass::Other(other) => format!("other: {other:?}"),
+    }
+}
+
+#[test]
+fn test_classify() {
+    assert_eq!(describe(Plant::Pine), "tree: Pine");
+    assert_eq!(describe(Plant::Oak { age: 3 }), "tree: Oak { age: 3 }");
+    assert_eq!(describe(Plant::Bamboo(2)), "bamboo of 2");
+    assert_eq!(describe(Plant::Moss), "other: Moss");
+}
+
+#[test]
+fn test_other_into_parent() {
+    assert_eq!(Plant::from(PlantOther::Moss), Plant::Moss);
+    assert_eq!(Plant::Pine.classify(), PlantClass::Tree(Tree::Pine),);
+}
+
+#[subenum(Num(Int, Float), Text, classify)]
+#[derive(Clone, Debug, PartialEq)]
+enum Value<'a, T> {
+    #[subenum(Int)]
+    Int(i64),
+    #[subenum(Float)]
+    Float(T),
+    #[subenum(Text)]
+    Str(&'a str),
+}
+
+#[test]
+fn test_classify_nested_and_generic() {
+    let value: Value<'_, f32> = Value::Float(1.5);
+    match value.classify() {
+        ValueClass::Num(num) => assert_eq!(num, Num::Float(1.5)),
+        ValueClass::Text(_) => unreachable!(),
+    }
+
+    let value: Value<'_, f32> = Value::Str("a");
+    assert_eq!(value.classify(), ValueClass::Text(Text::Str("a")));
+}
+
+#[subenum(Other, Common, classify)]
+#[derive(Clone, Debug, PartialEq)]
+enum Weed {
+    #[subenum(Other)]
+    Ivy,
+    #[subenum(Common)]
+    Dandelion,
+}
+
+#[test]
+fn test_classify_subenum_named_other() {
+    assert_eq!(Weed::Ivy.classify(), WeedClass::Other(Other::Ivy));
+    assert_eq!(
+        Weed::Dandelion.classify(),
+        WeedClass::Common(Common::Dandelion)
+    );
+}
@@ -0,0 +1,52 @@
+use subenum::subenum;
+
+#[subenum(Edible, Inedible = !Edible)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Edible)]
+    Basil(String),
+    #[subenum(Edible)]
+    Tomato,
+    Pine {
+        height: u32,
+    },
+    Grass,
+}
+
+#[test]
+fn test_complement_variants() {
+    let inedible = Inedible::Pine { height: 3 };
+    assert_eq!(Plant::from(inedible), Plant::Pine { height: 3 });
+    assert_eq!(Plant::from(Inedible::Grass), Plant::Grass);
+    assert_eq!(Plant::from(Edible::Tomato), Plant::Tomato);
+}
+
+#[test]
+fn test_complement_as_error() {
+    let err: Inedible = Edible::try_from(Plant::Pine { height: 3 }).unwrap_err();
+    assert_eq!(err, Inedible::Pine { height: 3 });
+
+    let err: Edible = Inedible::try_from(Plant::Basil("thai".into())).unwrap_err();
+    assert_eq!(err, Edible::Basil("thai".into()));
+
+    assert_eq!(Edible::try_from(Plant::Tomato), Ok(Edible::Tomato));
+}
+
+#[subenum(Num(Int), Other = !Num)]
+#[derive(Clone, Debug, PartialEq)]
+enum Value<'a, T> {
+    #[subenum(Int)]
+    Int(i64),
+    #[subenum(Num)]
+    Float(T),
+    Str(&'a str),
+}
+
+#[test]
+fn test_complement_generic() {
+    let value: Value<'_, f32> = Value::Str("a");
+    assert_eq!(Num::try_from(value), Err(Other::Str("a")));
+
+    let value: Value<'_, f32> = Value::Int(1);
+    assert_eq!(Other::try_from(value), Err(Num::Int(1)));
+}
@@ -0,0 +1,56 @@
+use subenum::subenum;
+
+#[subenum(Keyword)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Token {
+    #[subenum(Keyword)]
+    If,
+    #[subenum(Keyword)]
+    Else,
+    Int(i64),
+}
+
+const KEYWORDS: [Token; 2] = [Keyword::If.to_parent(), Keyword::Else.to_parent()];
+const FROM_PARENT: [Option<Keyword>; 2] = [
+    Keyword::try_from_parent(Token::Else),
+    Keyword::try_from_parent(Token::Int(1)),
+];
+
+#[test]
+fn test_const_fns() {
+    assert_eq!(KEYWORDS, [Token::If, Token::Else]);
+    assert_eq!(FROM_PARENT, [Some(Keyword::Else), None]);
+}
+
+#[subenum(Small)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Value<'a, T, U> {
+    #[subenum(Small)]
+    A(T),
+    B(&'a U),
+}
+
+const SMALL: Value<'static, u8, u16> = Small::A(1).to_parent();
+const NARROWED: Option<Small<u8>> = Small::try_from_parent(SMALL);
+
+#[test]
+fn test_const_fns_generic() {
+    assert_eq!(SMALL, Value::A(1));
+    assert_eq!(NARROWED, Some(Small::A(1)));
+    assert_eq!(Small::try_from_parent(Value::<u8, _>::B(&2)), None);
+}
+
+#[subenum(Light(derive(Copy)))]
+#[derive(Clone, Debug, PartialEq)]
+enum Heavy {
+    #[subenum(Light)]
+    Int(i64),
+    Text(String),
+}
+
+#[test]
+fn test_const_to_parent_only() {
+    const LIGHT: Light = Light::Int(2);
+    assert_eq!(LIGHT.to_parent(), Heavy::Int(2));
+    assert_ne!(Heavy::Text("text".into()), LIGHT);
+}
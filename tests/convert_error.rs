@@ -0,0 +1,104 @@
+use subenum::subenum;
+
+#[subenum(Edible)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Plant {
+    #[subenum(Edible)]
+    Basil(String),
+    #[subenum(Edible)]
+    Tomato,
+    Pine {
+        height: u32,
+    },
+}
+
+#[test]
+fn test_error_returns_parent() {
+    let plant = Plant::Pine { height: 30 };
+    let err = Edible::try_from(plant).unwrap_err();
+
+    assert_eq!(err.as_inner(), &Plant::Pine { height: 30 });
+    assert_eq!(err.into_inner(), Plant::Pine { height: 30 });
+}
+
+#[test]
+fn test_error_retry_without_clone() {
+    let plant = Plant::Basil("genovese".into());
+    let edible = Edible::try_from(plant).unwrap();
+    assert_eq!(edible, Plant::Basil("genovese".into()));
+
+    let plant = match Edible::try_from(Plant::Pine { height: 2 }) {
+        Ok(_) => unreachable!(),
+        Err(err) => err.into_inner(),
+    };
+    assert_eq!(plant, Plant::Pine { height: 2 });
+}
+
+#[test]
+fn test_error_display() {
+    let err = Edible::try_from(Plant::Pine { height: 1 }).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Plant::Pine is not a variant of Edible (expected one of: Basil, Tomato)"
+    );
+}
+
+#[test]
+fn test_error_metadata() {
+    let err = Edible::try_from(Plant::Pine { height: 1 }).unwrap_err();
+
+    assert_eq!(err.variant(), Some("Pine"));
+    assert_eq!(err.parent_name(), "Plant");
+    assert_eq!(err.child_name(), "Edible");
+    assert_eq!(err.expected(), &["Basil", "Tomato"]);
+}
+
+#[test]
+fn test_error_eq() {
+    let a = Edible::try_from(Plant::Pine { height: 1 }).unwrap_err();
+    let b = Edible::try_from(Plant::Pine { height: 1 }).unwrap_err();
+    let c = Edible::try_from(Plant::Pine { height: 2 }).unwrap_err();
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[cfg(feature = "error_trait")]
+#[test]
+fn test_error_trait() {
+    fn convert(plant: Plant) -> Result<Edible, Box<dyn std::error::Error>> {
+        Ok(Edible::try_from(plant)?)
+    }
+
+    assert!(convert(Plant::Tomato).is_ok());
+    assert!(convert(Plant::Pine { height: 1 }).is_err());
+}
+
+#[subenum(Small)]
+#[derive(Clone, Debug, PartialEq)]
+enum Generic<'a, T> {
+    #[subenum(Small)]
+    A(T),
+    B(&'a str),
+}
+
+#[test]
+fn test_generic_error() {
+    let err: SmallConvertError<Generic<'_, u8>> = Small::try_from(Generic::B("b")).unwrap_err();
+    assert_eq!(err.into_inner(), Generic::B("b"));
+}
+
+#[subenum(Few)]
+#[derive(Clone, Debug, PartialEq)]
+enum P {
+    #[subenum(Few)]
+    A,
+    B,
+}
+
+#[test]
+fn test_parent_named_like_error_param() {
+    let err: FewConvertError = Few::try_from(P::B).unwrap_err();
+    assert_eq!(err.into_inner(), P::B);
+    assert_eq!(Few::try_from(P::A), Ok(Few::A));
+}
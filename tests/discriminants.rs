@@ -0,0 +1,63 @@
+use subenum::subenum;
+
+#[subenum(
+    Storage,
+    Pack(discriminants = dense),
+    Num(discriminants = explicit),
+    Val(discriminants = inherit)
+)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+enum Type {
+    #[subenum(Storage, Pack)]
+    I16 = 0x77,
+    #[subenum(Storage, Pack)]
+    I8 = 0x78,
+    #[subenum(Storage, Val, Num(discriminant = 4))]
+    F32 = 0x7D,
+    #[subenum(Storage, Val, Num(discriminant = 1))]
+    I32 = 0x7F,
+    #[subenum(Storage, Val, Num)]
+    I64,
+}
+
+#[test]
+fn test_inherit() {
+    assert_eq!(Storage::I16 as u8, 0x77);
+    assert_eq!(Val::F32 as u8, 0x7D);
+    assert_eq!(Val::I32 as u8, 0x7F);
+}
+
+#[test]
+fn test_dense() {
+    assert_eq!(Pack::I16 as u8, 0);
+    assert_eq!(Pack::I8 as u8, 1);
+
+    let table = ["i16", "i8"];
+    assert_eq!(table[Pack::I8 as usize], "i8");
+}
+
+#[test]
+fn test_explicit() {
+    assert_eq!(Num::F32 as u8, 4);
+    assert_eq!(Num::I32 as u8, 1);
+    assert_eq!(Num::I64 as u8, 2);
+}
+
+#[test]
+fn test_conversions_use_parent_discriminants() {
+    assert_eq!(Type::from(Pack::I8), Type::I8);
+    assert_eq!(Pack::try_from(Type::I16), Ok(Pack::I16));
+    assert_eq!(Num::try_from(Type::I64), Ok(Num::I64));
+    assert_eq!(
+        Num::try_from(Storage::I8).unwrap_err().variant(),
+        Some("I8")
+    );
+    assert_eq!(Storage::from(Num::I32), Storage::I32);
+
+    assert_eq!(u8::from(Pack::I8), 0x78);
+    assert_eq!(u8::from(Num::I32), 0x7F);
+    assert_eq!(Pack::try_from(0x77u8), Ok(Pack::I16));
+    assert_eq!(Num::try_from(0x80u8), Ok(Num::I64));
+    assert!(Num::try_from(1u8).is_err());
+}
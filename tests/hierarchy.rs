@@ -0,0 +1,50 @@
+use subenum::subenum;
+
+#[subenum(Val(Num(Int, Float)), Pack)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum Storage<R = usize> {
+    #[subenum(Pack)]
+    I16(i16) = 0x77,
+    #[subenum(Pack)]
+    I8(i8) = 0x78,
+    #[subenum(Val(doc = "128-bit vector type (Val)\n"))]
+    V128([u8; 16]) = 0x7B,
+    #[subenum(Float)]
+    F64(f64) = 0x7C,
+    #[subenum(Float, Num(doc = "32-bit float type (Num)\n"))]
+    F32(f32) = 0x7D,
+    #[subenum(Int)]
+    I64(i64) = 0x7E,
+    #[subenum(Int)]
+    I32(i32) = 0x7F,
+    #[subenum(Val)]
+    Ref(R),
+}
+
+#[test]
+fn test_variants_propagate_to_ancestors() {
+    let _: Num = Num::I32(1);
+    let _: Num = Num::F32(1.0);
+    let _: Val = Val::I64(1);
+    let _: Val = Val::F64(1.0);
+    let _: Val = Val::V128([0; 16]);
+}
+
+#[test]
+fn test_edges() {
+    let int = Int::I32(7);
+
+    let num = Num::from(int);
+    assert_eq!(num, Num::I32(7));
+
+    let val: Val = Val::from(num);
+    assert_eq!(val, Val::I32(7));
+    assert_eq!(Val::<usize>::from(Float::F64(1.0)), Val::F64(1.0));
+
+    assert_eq!(Int::try_from(val).unwrap(), int);
+    assert!(Int::try_from(Num::F32(1.0)).is_err());
+    assert_eq!(Num::try_from(Val::<usize>::I64(3)).unwrap(), Num::I64(3));
+
+    assert_eq!(Storage::<usize>::from(int), Storage::I32(7));
+}
@@ -0,0 +1,55 @@
+use subenum::subenum;
+
+#[subenum(Edible, Tree)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Tree)]
+    Pine,
+    #[subenum(Edible)]
+    Basil,
+    #[subenum(Edible, Tree)]
+    Apple,
+    Moss,
+}
+
+#[test]
+fn test_child_constants() {
+    assert_eq!(Edible::COUNT, 2);
+    assert_eq!(Edible::ALL, [Edible::Basil, Edible::Apple]);
+    assert_eq!(Tree::ALL, [Tree::Pine, Tree::Apple]);
+    assert_eq!(Tree::Apple.index(), 1);
+
+    let heights: [u32; Tree::COUNT] = [30, 5];
+    assert_eq!(heights[Tree::Apple.index()], 5);
+}
+
+#[test]
+fn test_parent_constants() {
+    assert_eq!(Plant::COUNT, 4);
+    assert_eq!(
+        Plant::ALL,
+        [Plant::Pine, Plant::Basil, Plant::Apple, Plant::Moss]
+    );
+    for (i, plant) in Plant::ALL.into_iter().enumerate() {
+        assert_eq!(plant.index(), i);
+    }
+}
+
+#[subenum(Unit)]
+#[derive(Clone, Debug, PartialEq)]
+enum Mixed {
+    #[subenum(Unit)]
+    A,
+    #[subenum(Unit)]
+    B,
+    C(String),
+}
+
+#[test]
+fn test_fieldless_child_of_fielded_parent() {
+    const FIRST: usize = Unit::A.index();
+
+    assert_eq!(FIRST, 0);
+    assert_eq!(Unit::ALL, [Unit::A, Unit::B]);
+    assert_ne!(Mixed::C("c".into()), Unit::B);
+}
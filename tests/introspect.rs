@@ -0,0 +1,53 @@
+use subenum::subenum;
+
+#[subenum(Tree, Edible(Herb), introspect)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Plant<T> {
+    #[subenum(Tree, Edible)]
+    Apple,
+    #[subenum(Herb)]
+    Basil(T),
+    #[subenum(Tree)]
+    Pine {
+        height: u32,
+    },
+    Moss,
+}
+
+#[test]
+fn test_subenums() {
+    assert_eq!(Plant::Apple::<u8>.subenums(), &["Edible", "Tree"]);
+    assert_eq!(Plant::Basil(1).subenums(), &["Edible", "Herb"]);
+    assert_eq!(Plant::<u8>::Pine { height: 3 }.subenums(), &["Tree"]);
+    assert!(Plant::<u8>::Moss.subenums().is_empty());
+}
+
+#[test]
+fn test_belongs_to() {
+    assert!(Plant::Basil(1).belongs_to(PlantSubenum::Herb));
+    assert!(Plant::Basil(1).belongs_to(PlantSubenum::Edible));
+    assert!(!Plant::Basil(1).belongs_to(PlantSubenum::Tree));
+
+    let subenums: Vec<_> = PlantSubenum::ALL
+        .into_iter()
+        .filter(|&s| Plant::<u8>::Apple.belongs_to(s))
+        .collect();
+    assert_eq!(subenums, [PlantSubenum::Edible, PlantSubenum::Tree]);
+}
+
+#[test]
+fn test_const_table() {
+    assert_eq!(
+        Plant::<u8>::SUBENUMS,
+        &[
+            ("Apple", &["Edible", "Tree"][..]),
+            ("Basil", &["Edible", "Herb"]),
+            ("Pine", &["Tree"]),
+            ("Moss", &[]),
+        ]
+    );
+
+    assert_eq!(PlantSubenum::Herb.name(), "Herb");
+    assert_eq!(PlantSubenum::Edible.variants(), &["Apple", "Basil"]);
+    assert_eq!(PlantSubenum::Tree.variants(), &["Apple", "Pine"]);
+}
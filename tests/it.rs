@@ -52,7 +52,9 @@ enum Snoo<T> {
     A(T),
 }
 
+// The fallible conversion still works with every variant, via `From`.
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn test_snoo() {
     let a: Snoo<u32> = Snoo::A(3);
     let b: Floop<u32> = a.clone().try_into().unwrap();
@@ -100,7 +102,9 @@ enum EnumWithErrorVariant {
     Error,
 }
 
+// The fallible conversion still works with every variant, via `From`.
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn test_enum_with_error_variant() {
     let a = EnumWithErrorVariant::Error;
     let b = SubEnumWithErrorVariant::try_from(a).unwrap();
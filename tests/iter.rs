@@ -0,0 +1,66 @@
+use subenum::subenum;
+
+#[subenum(Binary, Literal, iter)]
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    #[subenum(Binary)]
+    Plus,
+    #[subenum(Binary)]
+    Minus,
+    #[subenum(Literal)]
+    Int(i64),
+    #[subenum(Literal)]
+    Str(String),
+}
+
+fn tokens() -> Vec<Token> {
+    vec![
+        Token::Plus,
+        Token::Int(1),
+        Token::Minus,
+        Token::Str("a".into()),
+    ]
+}
+
+#[test]
+fn test_filter() {
+    let binary: Vec<Binary> = tokens().into_iter().filter_binary().collect();
+    assert_eq!(binary, vec![Binary::Plus, Binary::Minus]);
+}
+
+#[test]
+fn test_map_while() {
+    let binary: Vec<Binary> = tokens().into_iter().map_while_binary().collect();
+    assert_eq!(binary, vec![Binary::Plus]);
+
+    let literals: Vec<Literal> = tokens().into_iter().map_while_literal().collect();
+    assert!(literals.is_empty());
+}
+
+#[test]
+fn test_partition() {
+    let (binary, rest): (Vec<Binary>, Vec<Token>) = tokens().into_iter().partition_binary();
+    assert_eq!(binary, vec![Binary::Plus, Binary::Minus]);
+    assert_eq!(rest, vec![Token::Int(1), Token::Str("a".into())]);
+}
+
+#[subenum(Num, Text = !Num, iter)]
+#[derive(Clone, Debug, PartialEq)]
+enum Value<'a, T: Copy> {
+    #[subenum(Num)]
+    Int(i64),
+    #[subenum(Num)]
+    Float(T),
+    Str(&'a str),
+}
+
+#[test]
+fn test_generic_iter() {
+    let values: Vec<Value<'_, f32>> = vec![Value::Int(1), Value::Str("a"), Value::Float(0.5)];
+
+    let nums: Vec<Num<f32>> = values.iter().cloned().filter_num().collect();
+    assert_eq!(nums, vec![Num::Int(1), Num::Float(0.5)]);
+
+    let texts: Vec<Text> = values.into_iter().filter_text().collect();
+    assert_eq!(texts, vec![Text::Str("a")]);
+}
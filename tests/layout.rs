@@ -0,0 +1,82 @@
+use subenum::subenum;
+
+#[subenum(Val(layout_compatible), Num)]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u8)]
+enum Storage {
+    I8(i8) = 0x78,
+    #[subenum(Val)]
+    V128([u8; 16]) = 0x7B,
+    #[subenum(Val, Num)]
+    F64(f64),
+    #[subenum(Val, Num)]
+    I32(i32) = 0x7F,
+    #[subenum(Val)]
+    Ref(Box<str>),
+}
+
+#[test]
+fn test_as_parent() {
+    let val = Val::Ref("big".into());
+    let storage: &Storage = val.as_parent();
+    assert_eq!(storage, &Storage::Ref("big".into()));
+
+    let val = Val::F64(1.5);
+    assert_eq!(val.as_parent(), &Storage::F64(1.5));
+    assert_eq!(
+        std::ptr::addr_of!(val).cast::<Storage>(),
+        val.as_parent() as *const _
+    );
+}
+
+#[test]
+fn test_ref_from_parent() {
+    let storage = Storage::V128([7; 16]);
+    let val: &Val = <&Val>::try_from(&storage).unwrap();
+    assert_eq!(val, &Val::V128([7; 16]));
+    assert!(std::ptr::eq(val.as_parent(), &storage));
+
+    let storage = Storage::I8(1);
+    let err = <&Val>::try_from(&storage).unwrap_err();
+    assert_eq!(err.variant(), Some("I8"));
+    assert_eq!(err.into_inner(), &Storage::I8(1));
+}
+
+#[subenum(Wide(layout_compatible))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C, u16)]
+enum Generic<T, U> {
+    #[subenum(Wide)]
+    A(T),
+    B(U),
+    #[subenum(Wide)]
+    C,
+}
+
+#[test]
+fn test_generic_layout() {
+    let wide: Wide<u64> = Wide::A(3);
+    assert_eq!(wide.as_parent::<u32>(), &Generic::A(3));
+
+    let parent: Generic<u64, u8> = Generic::C;
+    assert_eq!(<&Wide<u64>>::try_from(&parent), Ok(&Wide::C));
+    assert!(<&Wide<u64>>::try_from(&Generic::<u64, u8>::B(1)).is_err());
+}
+
+#[subenum(Loud(layout_compatible))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+enum Level {
+    Quiet,
+    #[subenum(Loud)]
+    High = 5,
+    #[subenum(Loud)]
+    Max,
+}
+
+#[test]
+fn test_c_layout() {
+    assert_eq!(Loud::Max.as_parent(), &Level::Max);
+    assert_eq!(<&Loud>::try_from(&Level::High), Ok(&Loud::High));
+    assert!(<&Loud>::try_from(&Level::Quiet).is_err());
+}
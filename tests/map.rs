@@ -0,0 +1,50 @@
+use subenum::subenum;
+
+#[subenum(Keyword(map), Literal(map), Token(map))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Token {
+    #[subenum(Literal)]
+    Int,
+    #[subenum(Keyword)]
+    If,
+    #[subenum(Literal)]
+    Str,
+    #[subenum(Keyword)]
+    Else,
+}
+
+#[test]
+fn test_map() {
+    let mut map = KeywordMap::from_fn(|keyword| keyword.index() * 10);
+    assert_eq!(map[Keyword::If], 0);
+    assert_eq!(map[Keyword::Else], 10);
+
+    map[Keyword::If] += 1;
+    *map.get_mut(Keyword::Else) += 2;
+    assert_eq!(*map.get(Keyword::If), 1);
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![(Keyword::If, &1), (Keyword::Else, &12)]
+    );
+
+    for (_, value) in map.iter_mut() {
+        *value = 0;
+    }
+    assert_eq!(map.into_values(), [0, 0]);
+    assert_eq!(format!("{map:?}"), "{If: 0, Else: 0}");
+}
+
+#[test]
+fn test_map_projection() {
+    let tokens = TokenMap::from_fn(|token| format!("{token:?}"));
+
+    let keywords = KeywordMap::from(tokens.clone());
+    assert_eq!(keywords[Keyword::If], "If");
+    assert_eq!(keywords[Keyword::Else], "Else");
+
+    let literals = LiteralMap::from(tokens);
+    assert_eq!(
+        literals.into_values(),
+        ["Int".to_string(), "Str".to_string()]
+    );
+}
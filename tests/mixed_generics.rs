@@ -2,6 +2,7 @@ use subenum::subenum;
 
 #[subenum(JustRef, JustConst, JustType)]
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::enum_variant_names)]
 enum KitchenSink<'a, T, const N: usize>
 where
     T: Copy + std::ops::Add<Output = T>,
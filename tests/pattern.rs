@@ -0,0 +1,150 @@
+use subenum::subenum;
+
+#[subenum(Edible, Tree)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Edible)]
+    Basil(String),
+    #[subenum(Edible)]
+    Tomato,
+    #[subenum(Edible, Tree)]
+    Apple { kind: String },
+    #[subenum(Tree)]
+    Pine,
+}
+
+fn describe(plant: &Plant) -> &'static str {
+    match plant {
+        edible_pat!() => "edible",
+        Plant::Pine => "tree",
+    }
+}
+
+#[test]
+fn test_pattern() {
+    assert_eq!(describe(&Plant::Tomato), "edible");
+    assert_eq!(
+        describe(&Plant::Apple {
+            kind: "fuji".into()
+        }),
+        "edible"
+    );
+    assert_eq!(describe(&Plant::Pine), "tree");
+
+    assert!(matches!(Plant::Pine, tree_pat!()));
+    assert!(!matches!(Plant::Basil("thai".into()), tree_pat!()));
+}
+
+#[test]
+fn test_pattern_binding() {
+    let plant = Plant::Basil("thai".into());
+
+    match plant {
+        edible_pat!(edible) => {
+            assert_eq!(
+                Edible::try_from(edible).unwrap(),
+                Edible::Basil("thai".into())
+            );
+        }
+        Plant::Pine => unreachable!(),
+    }
+}
+
+#[test]
+fn test_pattern_narrowing() {
+    fn narrow(plant: Plant) -> Option<Edible> {
+        edible_pat!(plant, edible => Some(edible), Plant::Pine => None)
+    }
+
+    assert_eq!(narrow(Plant::Tomato), Some(Edible::Tomato));
+    assert_eq!(
+        narrow(Plant::Basil("thai".into())),
+        Some(Edible::Basil("thai".into()))
+    );
+    assert_eq!(narrow(Plant::Pine), None);
+
+    let tree = tree_pat!(Plant::Pine, tree => tree, other => panic!("{other:?}"),);
+    assert_eq!(tree, Tree::Pine);
+}
+
+mod inner {
+    use subenum::subenum;
+
+    #[subenum(Small)]
+    #[derive(Debug)]
+    pub(crate) enum Size {
+        #[subenum(Small)]
+        Tiny,
+        #[subenum(Small)]
+        Little,
+        Huge,
+    }
+}
+
+#[test]
+fn test_pattern_path() {
+    use inner::Size;
+
+    assert!(matches!(Size::Little, inner::small_pat!()));
+    assert!(!matches!(Size::Huge, inner::small_pat!()));
+
+    let small = inner::small_pat!(Size::Tiny, small => Some(small), Size::Huge => None);
+    assert!(matches!(small, Some(inner::Small::Tiny)));
+}
+
+pub mod public {
+    use subenum::subenum;
+
+    #[subenum(Small(pat))]
+    pub enum Size {
+        #[subenum(Small)]
+        Tiny,
+        Huge,
+    }
+}
+
+#[test]
+fn test_pattern_public() {
+    use public::Size;
+
+    assert!(matches!(Size::Tiny, public::small_pat!()));
+    assert!(!matches!(Size::Huge, public::small_pat!()));
+}
+
+// Identical public enums in sibling modules, whose macros aren't exported
+// from the crate, don't clash.
+pub mod first {
+    use subenum::subenum;
+
+    #[subenum(Small)]
+    #[derive(Debug, PartialEq)]
+    pub enum Size {
+        #[subenum(Small)]
+        Tiny,
+        Huge,
+    }
+}
+
+pub mod second {
+    use subenum::subenum;
+
+    #[subenum(Small)]
+    #[derive(Debug, PartialEq)]
+    pub enum Size {
+        #[subenum(Small)]
+        Tiny,
+        Huge,
+    }
+}
+
+#[test]
+fn test_pattern_identical_enums() {
+    {
+        use first::Size;
+        assert!(matches!(Size::Tiny, first::small_pat!()));
+    }
+    {
+        use second::Size;
+        assert!(!matches!(Size::Huge, second::small_pat!()));
+    }
+}
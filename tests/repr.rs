@@ -0,0 +1,104 @@
+use subenum::subenum;
+
+#[subenum(Binary, Unary, Everything)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+enum Opcode {
+    #[subenum(Unary, Everything)]
+    Nop,
+    #[subenum(Binary, Everything)]
+    Add = 0x10,
+    #[subenum(Binary, Everything)]
+    Sub,
+    #[subenum(Unary, Everything)]
+    Neg = 0x20 + 2,
+    #[subenum(Everything)]
+    Halt,
+}
+
+#[test]
+fn test_to_repr() {
+    assert_eq!(u8::from(Unary::Nop), 0);
+    assert_eq!(u8::from(Binary::Add), 0x10);
+    assert_eq!(u8::from(Binary::Sub), 0x11);
+    assert_eq!(u8::from(Unary::Neg), 0x22);
+    assert_eq!(u8::from(Everything::Halt), 0x23);
+}
+
+#[test]
+fn test_from_repr() {
+    assert_eq!(Binary::try_from(0x11), Ok(Binary::Sub));
+    assert_eq!(Unary::try_from(0x22), Ok(Unary::Neg));
+    assert_eq!(Everything::try_from(0x23), Ok(Everything::Halt));
+
+    let err = Binary::try_from(0x22).unwrap_err();
+    assert_eq!(err.variant(), Some("Neg"));
+    assert_eq!(err.into_inner(), 0x22);
+
+    let err = Binary::try_from(0x05).unwrap_err();
+    assert_eq!(err.variant(), None);
+    assert_eq!(
+        err.to_string(),
+        "not a discriminant of Opcode (expected one of: Add, Sub)"
+    );
+}
+
+#[subenum(Pack, Val)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i16)]
+enum Storage {
+    #[subenum(Pack)]
+    I16(i16) = -2,
+    #[subenum(Pack)]
+    I8(i8),
+    #[subenum(Val)]
+    I32(i32),
+}
+
+#[subenum(Small)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i16)]
+enum Signed {
+    #[subenum(Small)]
+    A = -2,
+    #[subenum(Small)]
+    B,
+    C,
+}
+
+#[test]
+fn test_signed_repr() {
+    assert_eq!(i16::from(Small::A), -2);
+    assert_eq!(i16::from(Small::B), -1);
+    assert_eq!(Small::try_from(-1i16), Ok(Small::B));
+    assert_eq!(Small::try_from(0i16).unwrap_err().variant(), Some("C"));
+    assert_eq!(Signed::from(Small::B), Signed::B);
+    assert_ne!(Signed::C, Small::B);
+
+    // Children with fields have no conversions to their repr.
+    assert_eq!(Pack::try_from(Storage::I8(1)), Ok(Pack::I8(1)));
+    assert!(Val::try_from(Storage::I16(1)).is_err());
+}
+
+const NOP: u8 = 0;
+const ADD: u8 = 3;
+
+#[subenum(Arith)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+enum Named {
+    Nop = NOP,
+    #[subenum(Arith)]
+    Add = ADD,
+    #[subenum(Arith)]
+    Mul = ADD * 2,
+}
+
+#[test]
+fn test_discriminants_named_by_consts() {
+    assert_eq!(u8::from(Arith::Add), ADD);
+    assert_eq!(u8::from(Arith::Mul), 6);
+    assert_eq!(Arith::try_from(6), Ok(Arith::Mul));
+    assert_eq!(Arith::try_from(NOP).unwrap_err().variant(), Some("Nop"));
+    assert_eq!(Named::from(Arith::Add), Named::Add);
+}
@@ -0,0 +1,181 @@
+use subenum::subenum;
+
+#[subenum(
+    Low,
+    Dense(discriminants = dense),
+    Picked(discriminants = explicit),
+    High = !Low,
+    Every
+)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(i16)]
+enum Signal {
+    #[subenum(Low, Dense, Every)]
+    Off = -3,
+    #[subenum(Low, Dense, Picked(discriminant = 7), Every)]
+    Idle,
+    #[subenum(Low, Every)]
+    Wait,
+    #[subenum(Dense, Picked(discriminant = 8), Every)]
+    Busy = 10,
+    #[subenum(Every)]
+    Full,
+    #[subenum(Low, Dense, Picked(discriminant = 1), Every)]
+    Max = 32766,
+    #[subenum(Low, Every)]
+    Halt = 300,
+}
+
+#[test]
+fn test_inherited_runs() {
+    assert_eq!(Signal::from(Low::Off), Signal::Off);
+    assert_eq!(Signal::from(Low::Wait), Signal::Wait);
+    assert_eq!(Signal::from(Low::Halt), Signal::Halt);
+    assert_eq!(Low::try_from(Signal::Idle), Ok(Low::Idle));
+    assert_eq!(Low::try_from(Signal::Halt), Ok(Low::Halt));
+    assert_eq!(Low::Wait as i16, -1);
+    assert_eq!(Low::Max as i16, 32766);
+}
+
+#[test]
+fn test_rejected_runs() {
+    let err = Dense::try_from(Signal::Halt).unwrap_err();
+    assert_eq!(err.variant(), Some("Halt"));
+    assert_eq!(err.into_inner(), Signal::Halt);
+    assert_eq!(
+        Dense::try_from(Signal::Wait).unwrap_err().variant(),
+        Some("Wait")
+    );
+}
+
+#[test]
+fn test_dense_and_explicit_runs() {
+    assert_eq!(Dense::Busy as i16, 2);
+    assert_eq!(Dense::try_from(Signal::Busy), Ok(Dense::Busy));
+    assert_eq!(Dense::try_from(Signal::Off), Ok(Dense::Off));
+    assert_eq!(Signal::from(Dense::Idle), Signal::Idle);
+    assert_eq!(Signal::from(Dense::Max), Signal::Max);
+
+    assert_eq!(Picked::try_from(Signal::Idle), Ok(Picked::Idle));
+    assert_eq!(Picked::try_from(Signal::Busy), Ok(Picked::Busy));
+    assert_eq!(Signal::from(Picked::Busy), Signal::Busy);
+    assert_eq!(
+        Picked::try_from(Signal::Full).unwrap_err().variant(),
+        Some("Full")
+    );
+}
+
+#[test]
+fn test_complement_and_total_runs() {
+    assert_eq!(Low::try_from(Signal::Full), Err(High::Full));
+    assert_eq!(Low::try_from(Signal::Busy), Err(High::Busy));
+    assert_eq!(High::try_from(Signal::Off), Err(Low::Off));
+    assert_eq!(Signal::from(High::Full), Signal::Full);
+
+    for signal in Signal::ALL {
+        assert_eq!(Signal::from(Every::from(signal)), signal);
+    }
+}
+
+#[test]
+fn test_partial_eq_runs() {
+    assert_eq!(Dense::Busy, Signal::Busy);
+    assert_eq!(Signal::Max, Dense::Max);
+    assert_ne!(Dense::Busy, Signal::Full);
+    assert_ne!(Signal::Idle, Picked::Max);
+    assert_eq!(Picked::Max, Signal::Max);
+}
+
+#[test]
+fn test_sibling_runs() {
+    assert_eq!(Dense::try_from(Low::Idle), Ok(Dense::Idle));
+    assert_eq!(Dense::try_from(Low::Max), Ok(Dense::Max));
+    let err = Dense::try_from(Low::Halt).unwrap_err();
+    assert_eq!(err.variant(), Some("Halt"));
+    assert_eq!(err.into_inner(), Low::Halt);
+
+    assert_eq!(Every::from(Picked::Max), Every::Max);
+    assert_eq!(Picked::try_from(Dense::Busy), Ok(Picked::Busy));
+    assert_eq!(Dense::Busy, Picked::Busy);
+    assert_ne!(Dense::Off, Low::Idle);
+    assert_eq!(Low::Max, Dense::Max);
+}
+
+#[test]
+fn test_accessor_runs() {
+    assert!(Signal::Max.is_picked());
+    assert!(!Signal::Wait.is_picked());
+    assert!(Signal::Busy.is_high());
+    assert_eq!(Signal::Busy.into_dense(), Ok(Dense::Busy));
+    assert_eq!(Signal::Wait.into_dense(), Err(Signal::Wait));
+}
+
+#[test]
+fn test_repr_runs() {
+    assert_eq!(i16::from(Dense::Busy), 10);
+    assert_eq!(i16::from(Picked::Max), 32766);
+    assert_eq!(Dense::try_from(-2i16), Ok(Dense::Idle));
+    assert_eq!(Picked::try_from(11i16).unwrap_err().variant(), Some("Full"));
+    assert_eq!(Picked::try_from(12i16).unwrap_err().variant(), None);
+}
+
+const OFF: Signal = Low::Off.to_parent();
+const BUSY: Option<Dense> = Dense::try_from_parent(Signal::Busy);
+const FULL: Option<Dense> = Dense::try_from_parent(Signal::Full);
+
+#[test]
+fn test_const_and_unchecked_runs() {
+    assert_eq!(OFF, Signal::Off);
+    assert_eq!(BUSY, Some(Dense::Busy));
+    assert_eq!(FULL, None);
+    assert_eq!(
+        unsafe { Picked::from_parent_unchecked(Signal::Max) },
+        Picked::Max
+    );
+}
+
+#[subenum(Wide(layout_compatible), Narrow)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+enum Op {
+    #[subenum(Wide)]
+    Nop = 250,
+    #[subenum(Wide, Narrow)]
+    Add,
+    Sub,
+    #[subenum(Wide, Narrow)]
+    Mul,
+}
+
+#[test]
+fn test_layout_compatible_runs() {
+    assert_eq!(Wide::Mul as u8, 253);
+    assert_eq!(Wide::try_from(Op::Mul), Ok(Wide::Mul));
+    assert_eq!(Narrow::try_from(Wide::Mul), Ok(Narrow::Mul));
+    assert_eq!(Op::from(Narrow::Mul), Op::Mul);
+    assert!(Wide::try_from(Op::Sub).is_err());
+}
+
+#[subenum(Plant(repr(u16)), Edible, Tree)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Edible)]
+    Basil = 1,
+    #[subenum(Tree)]
+    Pine = 500,
+    #[subenum(Edible)]
+    Tomato = 7,
+}
+
+#[test]
+fn test_parent_only_repr() {
+    assert_eq!(Plant::Pine as u16, 500);
+    assert_eq!(Edible::try_from(Plant::Tomato), Ok(Edible::Tomato));
+    assert_eq!(Plant::from(Edible::Basil), Plant::Basil);
+    assert_eq!(Tree::try_from(Plant::Pine), Ok(Tree::Pine));
+    assert!(Edible::try_from(Plant::Pine).is_err());
+    assert_eq!(Edible::Tomato, Plant::Tomato);
+    assert!(Plant::Basil.is_edible());
+    assert_eq!(u16::from(Edible::Tomato), 7);
+    assert_eq!(Edible::try_from(1u16), Ok(Edible::Basil));
+}
@@ -0,0 +1,116 @@
+use subenum::subenum;
+
+#[subenum(Keyword(set), Literal(set), Token(set))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Token {
+    #[subenum(Literal)]
+    Int,
+    #[subenum(Keyword)]
+    If,
+    #[subenum(Literal)]
+    Str,
+    #[subenum(Keyword)]
+    Else,
+    #[subenum(Keyword)]
+    While,
+}
+
+#[test]
+fn test_set() {
+    let mut set = KeywordSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(Keyword::While));
+    assert!(set.insert(Keyword::If));
+    assert!(!set.insert(Keyword::If));
+
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(Keyword::If));
+    assert!(!set.contains(Keyword::Else));
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Keyword::If, Keyword::While]
+    );
+
+    assert!(set.remove(Keyword::If));
+    assert!(!set.remove(Keyword::If));
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![Keyword::While]);
+    assert_eq!(format!("{set:?}"), "{While}");
+}
+
+#[test]
+fn test_set_operations() {
+    let a: KeywordSet = [Keyword::If, Keyword::Else].into_iter().collect();
+    let b: KeywordSet = [Keyword::Else, Keyword::While].into_iter().collect();
+
+    assert_eq!(a.union(b), KeywordSet::all());
+    assert_eq!(
+        a.intersection(b).iter().collect::<Vec<_>>(),
+        vec![Keyword::Else]
+    );
+    assert_eq!(
+        a.difference(b).iter().collect::<Vec<_>>(),
+        vec![Keyword::If]
+    );
+
+    let mut c = a;
+    c.extend([Keyword::While]);
+    assert_eq!(c, KeywordSet::all());
+    assert_eq!(KeywordSet::all().len(), Keyword::COUNT);
+}
+
+#[test]
+fn test_set_conversions() {
+    let keywords: KeywordSet = [Keyword::Else, Keyword::While].into_iter().collect();
+    let tokens = TokenSet::from(keywords);
+    assert_eq!(
+        tokens.iter().collect::<Vec<_>>(),
+        vec![Token::Else, Token::While]
+    );
+    assert_eq!(KeywordSet::try_from(tokens), Ok(keywords));
+
+    let mut tokens = tokens;
+    tokens.insert(Token::Str);
+    let err = KeywordSet::try_from(tokens).unwrap_err();
+    assert_eq!(err.variant(), Some("Str"));
+    assert_eq!(err.into_inner(), tokens);
+
+    let literals: LiteralSet = [Literal::Str].into_iter().collect();
+    assert_eq!(
+        TokenSet::from(literals).iter().collect::<Vec<_>>(),
+        vec![Token::Str]
+    );
+}
+
+#[subenum(Even(set), Digit(set))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Digit {
+    #[subenum(Even)]
+    Zero,
+    One,
+    #[subenum(Even)]
+    Two,
+    Three,
+    #[subenum(Even)]
+    Four,
+    Five,
+    #[subenum(Even)]
+    Six,
+    Seven,
+    #[subenum(Even)]
+    Eight,
+    Nine,
+}
+
+#[test]
+fn test_wide_set() {
+    let all = DigitSet::all();
+    assert_eq!(all.len(), 10);
+    assert!(all.contains(Digit::Nine));
+
+    let evens = EvenSet::try_from(all.difference(DigitSet::from(EvenSet::all())));
+    assert_eq!(evens.unwrap_err().variant(), Some("One"));
+    assert_eq!(
+        DigitSet::from(EvenSet::all()).iter().last(),
+        Some(Digit::Eight)
+    );
+}
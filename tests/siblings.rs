@@ -0,0 +1,74 @@
+use subenum::subenum;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppleType {
+    CosmicCrisp,
+    Fuji,
+}
+
+#[subenum(Tree, Edible, Fruit, Grass)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Plant<'a, T> {
+    #[subenum(Tree, Edible, Fruit)]
+    Apple(AppleType),
+    #[subenum(Grass)]
+    Bamboo(&'a str),
+    #[subenum(Edible)]
+    Basil(T),
+    #[subenum(Tree)]
+    Fir,
+    #[subenum(Edible, Fruit)]
+    Tomato,
+    #[subenum(Edible, Grass)]
+    Wheat,
+}
+
+#[test]
+fn test_subset_from() {
+    let fruit = Fruit::Apple(AppleType::Fuji);
+    let edible: Edible<u8> = Edible::from(fruit);
+
+    assert_eq!(edible, Plant::<u8>::Apple(AppleType::Fuji));
+}
+
+#[test]
+fn test_overlap_try_from() {
+    let tree = Tree::Apple(AppleType::CosmicCrisp);
+    let fruit = Fruit::try_from(tree).unwrap();
+    assert_eq!(fruit, Fruit::Apple(AppleType::CosmicCrisp));
+
+    let err = Fruit::try_from(Tree::Fir).unwrap_err();
+    assert_eq!(err.variant(), Some("Fir"));
+    assert_eq!(err.into_inner(), Tree::Fir);
+
+    let edible: Edible<u8> = Edible::try_from(Grass::Wheat).unwrap();
+    assert_eq!(edible, Edible::Wheat);
+
+    let err = Grass::try_from(Edible::<u8>::Basil(3)).unwrap_err();
+    assert_eq!(err.into_inner(), Edible::Basil(3));
+}
+
+#[test]
+fn test_sibling_eq() {
+    let tree = Tree::Apple(AppleType::Fuji);
+    let edible: Edible<u8> = Edible::Apple(AppleType::Fuji);
+
+    assert_eq!(tree, edible);
+    assert_eq!(edible, tree);
+    assert_ne!(tree, Edible::<u8>::Apple(AppleType::CosmicCrisp));
+    assert_ne!(Tree::Fir, edible);
+    assert_ne!(Edible::<u8>::Tomato, Tree::Fir);
+}
+
+#[subenum(Left, Right)]
+#[derive(Debug, PartialEq)]
+enum Single {
+    #[subenum(Left, Right)]
+    Only,
+}
+
+#[test]
+fn test_identical_siblings() {
+    assert_eq!(Left::from(Right::Only), Left::Only);
+    assert_eq!(Left::Only, Right::Only);
+}
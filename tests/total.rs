@@ -0,0 +1,49 @@
+#![deny(unreachable_patterns)]
+
+use subenum::subenum;
+
+#[subenum(Everything(refs), Most)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant {
+    #[subenum(Everything, Most)]
+    Basil(String),
+    #[subenum(Everything, Most)]
+    Tomato,
+    #[subenum(Everything)]
+    Pine,
+}
+
+#[test]
+fn test_infallible_from_parent() {
+    let everything = Everything::from(Plant::Pine);
+    assert_eq!(everything, Everything::Pine);
+    assert_eq!(Plant::from(everything), Plant::Pine);
+
+    let everything: Everything = Plant::Tomato.into();
+    assert_eq!(everything, Everything::Tomato);
+}
+
+#[test]
+fn test_infallible_from_sibling() {
+    assert_eq!(Everything::from(Most::Tomato), Everything::Tomato);
+    assert!(Most::try_from(Everything::Pine).is_err());
+}
+
+#[test]
+fn test_infallible_views() {
+    let mut plant = Plant::Basil("thai".into());
+
+    assert!(matches!(
+        EverythingRef::from(&plant),
+        EverythingRef::Basil(_)
+    ));
+    assert!(matches!(
+        EverythingMut::from(&mut plant),
+        EverythingMut::Basil(_)
+    ));
+    assert_eq!(Everything::from(&plant), Everything::Basil("thai".into()));
+    assert_eq!(
+        plant.into_everything(),
+        Ok(Everything::Basil("thai".into()))
+    );
+}
@@ -0,0 +1,34 @@
+use subenum::subenum;
+
+#[subenum(Val, Everything)]
+#[derive(Clone, Debug, PartialEq)]
+enum Storage<'a, T, const N: usize>
+where
+    T: Clone,
+{
+    #[subenum(Val, Everything)]
+    Int(i64),
+    #[subenum(Val, Everything)]
+    Float(f64),
+    #[subenum(Everything)]
+    Ref(&'a [T; N]),
+}
+
+#[test]
+fn test_from_parent_unchecked() {
+    let storage: Storage<'_, u8, 2> = Storage::Float(1.5);
+    let val = unsafe { Val::from_parent_unchecked(storage) };
+    assert_eq!(val, Val::Float(1.5));
+
+    let array = [1u8, 2];
+    let everything = unsafe { Everything::from_parent_unchecked(Storage::Ref(&array)) };
+    assert_eq!(everything, Everything::Ref(&array));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "`Val::from_parent_unchecked` called with a `Storage`")]
+fn test_from_parent_unchecked_checks_in_debug() {
+    let array = [1u8, 2];
+    let _ = unsafe { Val::from_parent_unchecked(Storage::Ref(&array)) };
+}
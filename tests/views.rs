@@ -0,0 +1,77 @@
+use subenum::subenum;
+
+#[subenum(Edible(refs), Tree)]
+#[derive(Clone, Debug, PartialEq)]
+enum Plant<T> {
+    #[subenum(Edible)]
+    Basil(T),
+    #[subenum(Edible)]
+    Tomato,
+    #[subenum(Edible, Tree)]
+    Apple { kind: String, age: u32 },
+    #[subenum(Tree)]
+    Pine,
+}
+
+#[test]
+fn test_ref_view() {
+    let plant: Plant<u8> = Plant::Apple {
+        kind: "fuji".into(),
+        age: 3,
+    };
+
+    match EdibleRef::try_from(&plant).unwrap() {
+        EdibleRef::Apple { kind, age } => {
+            assert_eq!(kind, "fuji");
+            assert_eq!(*age, 3);
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+
+    let plant: Plant<u8> = Plant::Pine;
+    let err = EdibleRef::try_from(&plant).unwrap_err();
+    assert_eq!(err.variant(), Some("Pine"));
+    assert_eq!(err.into_inner(), &Plant::Pine);
+}
+
+#[test]
+fn test_mut_view() {
+    let mut plant = Plant::Basil(1u8);
+
+    if let Ok(EdibleMut::Basil(x)) = EdibleMut::try_from(&mut plant) {
+        *x += 1;
+    }
+    assert_eq!(plant, Plant::Basil(2));
+
+    let mut plant: Plant<u8> = Plant::Pine;
+    assert!(EdibleMut::try_from(&mut plant).is_err());
+}
+
+#[test]
+fn test_cloned_from_ref() {
+    let plant = Plant::Basil(7u8);
+    let edible = Edible::try_from(&plant).unwrap();
+
+    assert_eq!(edible, Edible::Basil(7));
+    assert_eq!(plant, edible);
+}
+
+#[subenum(Borrowed(refs))]
+#[derive(Debug)]
+enum Expression<'a, T: Copy> {
+    #[subenum(Borrowed)]
+    Lit(&'a str),
+    #[subenum(Borrowed)]
+    Value(T),
+    Nothing,
+}
+
+#[test]
+fn test_view_with_lifetime() {
+    let expr: Expression<'_, i32> = Expression::Lit("x");
+    let view = BorrowedRef::try_from(&expr).unwrap();
+    let copy = view;
+
+    assert!(matches!(copy, BorrowedRef::Lit(&"x")));
+    assert!(BorrowedRef::try_from(&Expression::<i32>::Nothing).is_err());
+}